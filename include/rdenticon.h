@@ -0,0 +1,333 @@
+/* Generated by cbindgen from src/capi.rs. Do not edit by hand. */
+
+#ifndef RDENTICON_H
+#define RDENTICON_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Error codes returned by the C bindings.
+typedef enum RdenticonError {
+  // The call succeeded.
+  RDENTICON_ERROR_OK = 0,
+  // The hues are not within the range `[0.0, 360.0)`.
+  RDENTICON_ERROR_INVALID_HUES,
+  // The color lightness is not within the range `0.0..=1.0`, or its start is greater than
+  // its end.
+  RDENTICON_ERROR_INVALID_COLOR_LIGHTNESS,
+  // The grayscale lightness is not within the range `0.0..=1.0`, or its start is greater than
+  // its end.
+  RDENTICON_ERROR_INVALID_GRAYSCALE_LIGHTNESS,
+  // The color saturation is not within the range `[0.0, 1.0]`.
+  RDENTICON_ERROR_INVALID_COLOR_SATURATION,
+  // The grayscale saturation is not within the range `[0.0, 1.0]`.
+  RDENTICON_ERROR_INVALID_GRAYSCALE_SATURATION,
+  // The padding is not within the range `[0.0, 0.5]`.
+  RDENTICON_ERROR_INVALID_PADDING,
+  // A color is not a valid hex color.
+  RDENTICON_ERROR_MALFORMED_COLOR,
+  // A jdenticon configuration is not valid JSON.
+  RDENTICON_ERROR_MALFORMED_JSON,
+  // The size of the icon is zero.
+  RDENTICON_ERROR_ZERO_SIZE,
+  // The destination buffer is too small to hold the icon.
+  RDENTICON_ERROR_DESTINATION_TOO_SMALL,
+  // A required pointer argument is null.
+  RDENTICON_ERROR_NULL_POINTER,
+  // A string argument is not valid UTF-8.
+  RDENTICON_ERROR_INVALID_UTF8,
+  // The grid size is not within the range `4..=10`, or is not `4` in jdenticon compatibility
+  // mode.
+  RDENTICON_ERROR_INVALID_GRID_SIZE,
+  // The shape set has no outer shapes or no center shapes.
+  RDENTICON_ERROR_EMPTY_SHAPE_SET,
+  // The minimum contrast ratio is not within the range `[1.0, 21.0]`.
+  RDENTICON_ERROR_INVALID_MIN_CONTRAST,
+  // The supersampling factor is not within the range `1..=16`, or the supersampled icon is too
+  // large to be held in memory.
+  RDENTICON_ERROR_INVALID_SUPERSAMPLING,
+  // An enum argument is not one of the values of its enum, or a boolean argument is neither `0`
+  // nor `1`.
+  RDENTICON_ERROR_INVALID_ARGUMENT,
+  // The icon is too large to be held in memory.
+  RDENTICON_ERROR_TOO_LARGE,
+} RdenticonError;
+
+// How the icon's grid is placed within non-square icons. See `Alignment` for more information.
+typedef enum RdenticonAlignment {
+  // The grid is kept square and centered along the longer axis.
+  RDENTICON_ALIGNMENT_CENTER,
+  // The grid is stretched to fill the padded area.
+  RDENTICON_ALIGNMENT_FIT,
+  // The grid is kept square and repeated along the longer axis.
+  RDENTICON_ALIGNMENT_TILE,
+} RdenticonAlignment;
+
+// How shapes are anti-aliased in raster output. See `Antialiasing` for more information.
+typedef enum RdenticonAntialiasing {
+  // Shapes are drawn without anti-aliasing.
+  RDENTICON_ANTIALIASING_NONE,
+  // Pixels are blended by the exact fraction of their area that shapes cover.
+  RDENTICON_ANTIALIASING_COVERAGE,
+  // Shapes are supersampled and downscaled with a box filter.
+  RDENTICON_ANTIALIASING_SUPERSAMPLE_BOX,
+  // Shapes are supersampled and downscaled with a Lanczos filter with 3 lobes.
+  RDENTICON_ANTIALIASING_SUPERSAMPLE_LANCZOS3,
+} RdenticonAntialiasing;
+
+// The color space that colors are derived from a hue in. See `ColorSpace` for more information.
+typedef enum RdenticonColorSpace {
+  // HSL, with lightness corrected for each hue the same way jdenticon does.
+  RDENTICON_COLOR_SPACE_HSL,
+  // OKLCH, with perceptually uniform lightness.
+  RDENTICON_COLOR_SPACE_OKLCH,
+  // CIELCh with a D65 white point.
+  RDENTICON_COLOR_SPACE_CIELCH,
+} RdenticonColorSpace;
+
+// An opaque handle to a configuration.
+typedef struct RdenticonConfig RdenticonConfig;
+
+// Fixed colors of an identicon, each as `{r, g, b, a}`. See `Palette` for more information.
+typedef struct RdenticonPalette {
+  // A dark grayscale color.
+  uint8_t dark_gray[4];
+  // A color of medium lightness.
+  uint8_t mid_color[4];
+  // A light grayscale color.
+  uint8_t light_gray[4];
+  // A light color.
+  uint8_t light_color[4];
+  // A dark color.
+  uint8_t dark_color[4];
+} RdenticonPalette;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns a static, NUL-terminated description of the given `RdenticonError` code.
+const char *rdenticon_error_message(uint32_t error);
+
+// Creates a new configuration with default values. Free it with [`rdenticon_config_free`].
+struct RdenticonConfig *rdenticon_config_new(void);
+
+// Frees a configuration created with [`rdenticon_config_new`]. Does nothing if `config` is null.
+//
+// # Safety
+// `config` must be null or a pointer returned by [`rdenticon_config_new`] that was not freed.
+void rdenticon_config_free(struct RdenticonConfig *config);
+
+// Sets the hues to be used in the identicon, in degrees. `hues` may be null if `len` is zero,
+// in which case all hues are allowed.
+//
+// # Safety
+// `config` must be a valid configuration, and `hues` must point to `len` doubles.
+enum RdenticonError rdenticon_config_set_hues(struct RdenticonConfig *config,
+                                              const double *hues,
+                                              size_t len);
+
+// Sets the color space that colors are derived from a hue in, as one of the
+// `RdenticonColorSpace` values.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_color_space(struct RdenticonConfig *config,
+                                                     uint32_t color_space);
+
+// Sets fixed palettes to pick the colors of the identicon from. `palettes` may be null if `len`
+// is zero, in which case colors are derived from a hue.
+//
+// # Safety
+// `config` must be a valid configuration, and `palettes` must point to `len` palettes.
+enum RdenticonError rdenticon_config_set_palettes(struct RdenticonConfig *config,
+                                                  const struct RdenticonPalette *palettes,
+                                                  size_t len);
+
+// Sets the lightness range of colored shapes in the identicon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_color_lightness(struct RdenticonConfig *config,
+                                                         double min,
+                                                         double max);
+
+// Sets the lightness range of grayscale shapes in the identicon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_grayscale_lightness(struct RdenticonConfig *config,
+                                                             double min,
+                                                             double max);
+
+// Sets the saturation of colored shapes in the identicon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_color_saturation(struct RdenticonConfig *config,
+                                                          double saturation);
+
+// Sets the saturation of grayscale shapes in the identicon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_grayscale_saturation(struct RdenticonConfig *config,
+                                                              double saturation);
+
+// Sets the background color to be rendered behind the identicon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_background_color(struct RdenticonConfig *config,
+                                                          uint8_t r,
+                                                          uint8_t g,
+                                                          uint8_t b,
+                                                          uint8_t a);
+
+// Sets the minimum WCAG contrast ratio of the colors of the identicon against the background
+// color. Pass `0.0` to disable the check, which is the default.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_min_contrast(struct RdenticonConfig *config, double ratio);
+
+// Sets whether to mirror the lightness of every color role, as `0` for false or `1` for true.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_mirror_lightness(struct RdenticonConfig *config,
+                                                          uint32_t mirror);
+
+// Sets the padding surrounding the icon relative to the size of the icon.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_padding(struct RdenticonConfig *config, double padding);
+
+// Sets both the width and height of the icon in pixels.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_size(struct RdenticonConfig *config, uint32_t size);
+
+// Sets the width of the icon in pixels.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_width(struct RdenticonConfig *config, uint32_t width);
+
+// Sets the height of the icon in pixels.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_height(struct RdenticonConfig *config, uint32_t height);
+
+// Sets how the icon's grid is placed within non-square icons, as one of the
+// `RdenticonAlignment` values.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_alignment(struct RdenticonConfig *config,
+                                                   uint32_t alignment);
+
+// Sets how shapes are anti-aliased in raster output, as one of the `RdenticonAntialiasing`
+// values. `factor` is how many times larger than the
+// icon shapes are supersampled along each axis, within the range `1..=16`, and is ignored unless
+// `antialiasing` is one of the supersampling modes.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_antialiasing(struct RdenticonConfig *config,
+                                                      uint32_t antialiasing,
+                                                      uint32_t factor);
+
+// Sets the amount of cells along each side of the icon's grid, within the range `4..=10`.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_grid_size(struct RdenticonConfig *config,
+                                                   uint32_t grid_size);
+
+// Sets whether to strictly follow jdenticon's geometry and color conversion, as `0` for false
+// or `1` for true.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_set_jdenticon_compat(struct RdenticonConfig *config,
+                                                          uint32_t jdenticon_compat);
+
+// Validates the configuration, returning the same errors as `ConfigBuilder::build`.
+//
+// # Safety
+// `config` must be a valid configuration.
+enum RdenticonError rdenticon_config_build(const struct RdenticonConfig *config);
+
+// Renders an identicon for a 20-byte hash as `width * height` RGBA pixels, row by row. On
+// success, the buffer is written to `out` and its length in bytes to `out_len`. Free the buffer
+// with [`rdenticon_rgba_free`]. The pixels are the same as those of `render_identicon`, and of
+// the other bindings, for the same hash and configuration.
+//
+// Returns `RdenticonError::TooLarge` if the buffer cannot be allocated.
+//
+// # Safety
+// `hash` must point to 20 bytes, `config` must be a valid configuration, and `out` and
+// `out_len` must be valid for writes.
+enum RdenticonError rdenticon_render_rgba(const uint8_t *hash,
+                                          const struct RdenticonConfig *config,
+                                          uint8_t **out,
+                                          size_t *out_len);
+
+// Generates an identicon for a NUL-terminated UTF-8 message, hashed with SHA-1, as RGBA pixels.
+// See [`rdenticon_render_rgba`] for more information.
+//
+// # Safety
+// `message` must be a valid NUL-terminated string, `config` must be a valid configuration, and
+// `out` and `out_len` must be valid for writes.
+enum RdenticonError rdenticon_generate_rgba(const char *message,
+                                            const struct RdenticonConfig *config,
+                                            uint8_t **out,
+                                            size_t *out_len);
+
+// Renders an identicon for a 20-byte hash as an SVG document. On success, a NUL-terminated
+// string is written to `out`. Free it with [`rdenticon_string_free`].
+//
+// # Safety
+// `hash` must point to 20 bytes, `config` must be a valid configuration, and `out` must be
+// valid for writes.
+enum RdenticonError rdenticon_render_svg(const uint8_t *hash,
+                                         const struct RdenticonConfig *config,
+                                         char **out);
+
+// Generates an identicon for a NUL-terminated UTF-8 message, hashed with SHA-1, as an SVG
+// document. See [`rdenticon_render_svg`] for more information.
+//
+// # Safety
+// `message` must be a valid NUL-terminated string, `config` must be a valid configuration, and
+// `out` must be valid for writes.
+enum RdenticonError rdenticon_generate_svg(const char *message,
+                                           const struct RdenticonConfig *config,
+                                           char **out);
+
+// Frees a buffer returned by [`rdenticon_render_rgba`] or [`rdenticon_generate_rgba`]. Does
+// nothing if `buffer` is null.
+//
+// # Safety
+// `buffer` must be null or a buffer returned by rdenticon that was not freed, and `len` must be
+// the length that was returned with it.
+void rdenticon_rgba_free(uint8_t *buffer, size_t len);
+
+// Frees a string returned by [`rdenticon_render_svg`] or [`rdenticon_generate_svg`]. Does
+// nothing if `string` is null.
+//
+// # Safety
+// `string` must be null or a string returned by rdenticon that was not freed.
+void rdenticon_string_free(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* RDENTICON_H */
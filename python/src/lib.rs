@@ -0,0 +1,630 @@
+//! Python bindings for rdenticon.
+//!
+//! Build and install them into the current environment with `maturin develop` from this
+//! directory. Invalid configurations raise `ValueError` with the message of the corresponding
+//! [`ConfigBuilderError`](rdenticon::ConfigBuilderError).
+
+use pyo3::{
+    exceptions::PyValueError,
+    prelude::*,
+    types::{PyBytes, PyDict},
+};
+use rdenticon::{
+    describe, reduce_digest, render_identicon_svg, try_render_identicon, Alignment, Antialiasing,
+    ColorSpace, Config, DownscaleFilter, IdenticonHasher, IdenticonInput, ImageFormat, Palette,
+    Rgba, Sha1,
+};
+use std::fmt::Display;
+
+fn value_error(err: impl Display) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// A color given as an `(r, g, b)` or `(r, g, b, a)` tuple.
+#[derive(FromPyObject)]
+enum Color {
+    Rgb((u8, u8, u8)),
+    Rgba((u8, u8, u8, u8)),
+}
+
+impl From<Color> for Rgba {
+    fn from(color: Color) -> Self {
+        match color {
+            Color::Rgb((r, g, b)) => Self { r, g, b, a: 255 },
+            Color::Rgba((r, g, b, a)) => Self { r, g, b, a },
+        }
+    }
+}
+
+/// A palette given as a dict with a color for each of `dark_gray`, `mid_color`, `light_gray`,
+/// `light_color` and `dark_color`.
+#[derive(FromPyObject)]
+#[pyo3(from_item_all)]
+struct PyPalette {
+    dark_gray: Color,
+    mid_color: Color,
+    light_gray: Color,
+    light_color: Color,
+    dark_color: Color,
+}
+
+impl From<PyPalette> for Palette {
+    fn from(palette: PyPalette) -> Self {
+        Self {
+            dark_gray: palette.dark_gray.into(),
+            mid_color: palette.mid_color.into(),
+            light_gray: palette.light_gray.into(),
+            light_color: palette.light_color.into(),
+            dark_color: palette.dark_color.into(),
+        }
+    }
+}
+
+/// How the grid is placed within non-square icons, given as `"center"`, `"fit"` or `"tile"`.
+fn parse_alignment(alignment: &str) -> PyResult<Alignment> {
+    match alignment {
+        "center" => Ok(Alignment::Center),
+        "fit" => Ok(Alignment::Fit),
+        "tile" => Ok(Alignment::Tile),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown alignment {alignment:?}, expected \"center\", \"fit\" or \"tile\""
+        ))),
+    }
+}
+
+const fn alignment_name(alignment: Alignment) -> &'static str {
+    match alignment {
+        Alignment::Center => "center",
+        Alignment::Fit => "fit",
+        Alignment::Tile => "tile",
+    }
+}
+
+const fn rgba_tuple(Rgba { r, g, b, a }: Rgba) -> (u8, u8, u8, u8) {
+    (r, g, b, a)
+}
+
+/// A color space, given as `"hsl"`, `"oklch"` or `"cielch"`.
+fn parse_color_space(color_space: &str) -> PyResult<ColorSpace> {
+    match color_space {
+        "hsl" => Ok(ColorSpace::Hsl),
+        "oklch" => Ok(ColorSpace::Oklch),
+        "cielch" => Ok(ColorSpace::Cielch),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown color space {color_space:?}, expected \"hsl\", \"oklch\" or \"cielch\""
+        ))),
+    }
+}
+
+const fn color_space_name(color_space: ColorSpace) -> &'static str {
+    match color_space {
+        ColorSpace::Hsl => "hsl",
+        ColorSpace::Oklch => "oklch",
+        ColorSpace::Cielch => "cielch",
+    }
+}
+
+/// How shapes are anti-aliased, given as `"none"`, `"coverage"`, or a downscale filter and a
+/// supersampling factor such as `"box:4"` or `"lanczos3:4"`.
+fn parse_antialiasing(antialiasing: &str) -> PyResult<Antialiasing> {
+    let supersample = |factor: &str, filter| {
+        let factor = factor.parse().map_err(|_| {
+            PyValueError::new_err(format!("invalid supersampling factor {factor:?}"))
+        })?;
+        Ok(Antialiasing::Supersample { factor, filter })
+    };
+
+    match antialiasing.split_once(':') {
+        None if antialiasing == "none" => Ok(Antialiasing::None),
+        None if antialiasing == "coverage" => Ok(Antialiasing::Coverage),
+        Some(("box", factor)) => supersample(factor, DownscaleFilter::Box),
+        Some(("lanczos3", factor)) => supersample(factor, DownscaleFilter::Lanczos3),
+        _ => Err(PyValueError::new_err(format!(
+            "unknown antialiasing {antialiasing:?}, expected \"none\", \"coverage\", \"box:N\" \
+             or \"lanczos3:N\""
+        ))),
+    }
+}
+
+fn antialiasing_name(antialiasing: Antialiasing) -> String {
+    match antialiasing {
+        Antialiasing::None => "none".to_string(),
+        Antialiasing::Coverage => "coverage".to_string(),
+        Antialiasing::Supersample { factor, filter } => match filter {
+            DownscaleFilter::Box => format!("box:{factor}"),
+            DownscaleFilter::Lanczos3 => format!("lanczos3:{factor}"),
+        },
+    }
+}
+
+/// A message to generate an identicon for. See [`IdenticonInput`] for how each type is encoded.
+#[derive(FromPyObject)]
+enum Message {
+    Str(String),
+    Bytes(Vec<u8>),
+    Int(i128),
+}
+
+impl Message {
+    fn hash(&self) -> [u8; 20] {
+        let bytes = match self {
+            Self::Str(message) => message.identicon_bytes(),
+            Self::Bytes(message) => message.identicon_bytes(),
+            Self::Int(message) => message.identicon_bytes(),
+        };
+        reduce_digest(Sha1::digest(&bytes).as_ref())
+    }
+}
+
+/// Configuration variables for rendering identicons.
+///
+/// All arguments are keyword-only and optional, and match the methods of `ConfigBuilder`.
+/// `size` sets both `width` and `height`, which take precedence over it. Raises `ValueError` if
+/// the configuration is invalid.
+#[pyclass(name = "Config", module = "rdenticon", frozen)]
+#[derive(Clone)]
+struct PyConfig(Config);
+
+#[pymethods]
+impl PyConfig {
+    #[new]
+    #[pyo3(signature = (
+        *,
+        hues = None,
+        color_lightness = None,
+        grayscale_lightness = None,
+        color_saturation = None,
+        grayscale_saturation = None,
+        color_space = None,
+        palettes = None,
+        background_color = None,
+        min_contrast = None,
+        mirror_lightness = None,
+        padding = None,
+        size = None,
+        width = None,
+        height = None,
+        alignment = None,
+        antialiasing = None,
+        grid_size = None,
+        jdenticon_compat = None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        hues: Option<Vec<f64>>,
+        color_lightness: Option<(f64, f64)>,
+        grayscale_lightness: Option<(f64, f64)>,
+        color_saturation: Option<f64>,
+        grayscale_saturation: Option<f64>,
+        color_space: Option<&str>,
+        palettes: Option<Vec<PyPalette>>,
+        background_color: Option<Color>,
+        min_contrast: Option<f64>,
+        mirror_lightness: Option<bool>,
+        padding: Option<f64>,
+        size: Option<u32>,
+        width: Option<u32>,
+        height: Option<u32>,
+        alignment: Option<&str>,
+        antialiasing: Option<&str>,
+        grid_size: Option<u32>,
+        jdenticon_compat: Option<bool>,
+    ) -> PyResult<Self> {
+        let default = Config::default();
+        let config = Config {
+            hues: hues.unwrap_or(default.hues),
+            color_lightness: color_lightness
+                .map_or(default.color_lightness, |(min, max)| min..=max),
+            grayscale_lightness: grayscale_lightness
+                .map_or(default.grayscale_lightness, |(min, max)| min..=max),
+            color_saturation: color_saturation.unwrap_or(default.color_saturation),
+            grayscale_saturation: grayscale_saturation.unwrap_or(default.grayscale_saturation),
+            color_space: color_space.map_or(Ok(default.color_space), parse_color_space)?,
+            palettes: palettes.map_or(default.palettes, |palettes| {
+                palettes.into_iter().map(Palette::from).collect()
+            }),
+            background_color: background_color.map_or(default.background_color, Rgba::from),
+            min_contrast,
+            mirror_lightness: mirror_lightness.unwrap_or(default.mirror_lightness),
+            padding: padding.unwrap_or(default.padding),
+            width: width.or(size).unwrap_or(default.width),
+            height: height.or(size).unwrap_or(default.height),
+            alignment: alignment.map_or(Ok(default.alignment), parse_alignment)?,
+            antialiasing: antialiasing.map_or(Ok(default.antialiasing), parse_antialiasing)?,
+            grid_size: grid_size.unwrap_or(default.grid_size),
+            shapes: default.shapes,
+            jdenticon_compat: jdenticon_compat.unwrap_or(default.jdenticon_compat),
+        };
+        config.validate().map_err(value_error)?;
+
+        Ok(Self(config))
+    }
+
+    /// Creates a new `ConfigBuilder` with default values.
+    #[staticmethod]
+    fn builder() -> PyConfigBuilder {
+        PyConfigBuilder(Config::default())
+    }
+
+    #[getter]
+    fn hues(&self) -> Vec<f64> {
+        self.0.hues.clone()
+    }
+
+    #[getter]
+    fn color_lightness(&self) -> (f64, f64) {
+        (
+            *self.0.color_lightness.start(),
+            *self.0.color_lightness.end(),
+        )
+    }
+
+    #[getter]
+    fn grayscale_lightness(&self) -> (f64, f64) {
+        let range = &self.0.grayscale_lightness;
+        (*range.start(), *range.end())
+    }
+
+    #[getter]
+    fn color_saturation(&self) -> f64 {
+        self.0.color_saturation
+    }
+
+    #[getter]
+    fn grayscale_saturation(&self) -> f64 {
+        self.0.grayscale_saturation
+    }
+
+    #[getter]
+    fn color_space(&self) -> &'static str {
+        color_space_name(self.0.color_space)
+    }
+
+    #[getter]
+    fn palettes<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.0
+            .palettes
+            .iter()
+            .map(|palette| {
+                let dict = PyDict::new(py);
+                dict.set_item("dark_gray", rgba_tuple(palette.dark_gray))?;
+                dict.set_item("mid_color", rgba_tuple(palette.mid_color))?;
+                dict.set_item("light_gray", rgba_tuple(palette.light_gray))?;
+                dict.set_item("light_color", rgba_tuple(palette.light_color))?;
+                dict.set_item("dark_color", rgba_tuple(palette.dark_color))?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    #[getter]
+    fn background_color(&self) -> (u8, u8, u8, u8) {
+        rgba_tuple(self.0.background_color)
+    }
+
+    #[getter]
+    fn min_contrast(&self) -> Option<f64> {
+        self.0.min_contrast
+    }
+
+    #[getter]
+    fn mirror_lightness(&self) -> bool {
+        self.0.mirror_lightness
+    }
+
+    #[getter]
+    fn padding(&self) -> f64 {
+        self.0.padding
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.0.width
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.0.height
+    }
+
+    #[getter]
+    fn alignment(&self) -> &'static str {
+        alignment_name(self.0.alignment)
+    }
+
+    #[getter]
+    fn antialiasing(&self) -> String {
+        antialiasing_name(self.0.antialiasing)
+    }
+
+    #[getter]
+    fn grid_size(&self) -> u32 {
+        self.0.grid_size
+    }
+
+    #[getter]
+    fn jdenticon_compat(&self) -> bool {
+        self.0.jdenticon_compat
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Config(hues={:?}, color_lightness={:?}, grayscale_lightness={:?}, \
+             color_saturation={:?}, grayscale_saturation={:?}, color_space='{}', \
+             background_color={:?}, padding={:?}, width={}, height={}, alignment='{}', \
+             antialiasing='{}', grid_size={}, jdenticon_compat={})",
+            self.0.hues,
+            self.color_lightness(),
+            self.grayscale_lightness(),
+            self.0.color_saturation,
+            self.0.grayscale_saturation,
+            self.color_space(),
+            self.background_color(),
+            self.0.padding,
+            self.0.width,
+            self.0.height,
+            self.alignment(),
+            self.antialiasing(),
+            self.0.grid_size,
+            if self.0.jdenticon_compat {
+                "True"
+            } else {
+                "False"
+            },
+        )
+    }
+}
+
+/// A builder for `Config`s. Every method returns a new builder, so calls can be chained.
+#[pyclass(name = "ConfigBuilder", module = "rdenticon", frozen)]
+#[derive(Clone)]
+struct PyConfigBuilder(Config);
+
+impl PyConfigBuilder {
+    fn with(&self, f: impl FnOnce(&mut Config)) -> Self {
+        let mut config = self.0.clone();
+        f(&mut config);
+        Self(config)
+    }
+}
+
+#[pymethods]
+impl PyConfigBuilder {
+    #[new]
+    fn new() -> Self {
+        Self(Config::default())
+    }
+
+    fn hues(&self, hues: Vec<f64>) -> Self {
+        self.with(|config| config.hues = hues)
+    }
+
+    fn color_lightness(&self, min: f64, max: f64) -> Self {
+        self.with(|config| config.color_lightness = min..=max)
+    }
+
+    fn grayscale_lightness(&self, min: f64, max: f64) -> Self {
+        self.with(|config| config.grayscale_lightness = min..=max)
+    }
+
+    fn color_saturation(&self, saturation: f64) -> Self {
+        self.with(|config| config.color_saturation = saturation)
+    }
+
+    fn grayscale_saturation(&self, saturation: f64) -> Self {
+        self.with(|config| config.grayscale_saturation = saturation)
+    }
+
+    fn color_space(&self, color_space: &str) -> PyResult<Self> {
+        let color_space = parse_color_space(color_space)?;
+        Ok(self.with(|config| config.color_space = color_space))
+    }
+
+    fn palettes(&self, palettes: Vec<PyPalette>) -> Self {
+        let palettes = palettes.into_iter().map(Palette::from).collect();
+        self.with(|config| config.palettes = palettes)
+    }
+
+    fn background_color(&self, color: Color) -> Self {
+        self.with(|config| config.background_color = color.into())
+    }
+
+    fn min_contrast(&self, ratio: f64) -> Self {
+        self.with(|config| config.min_contrast = Some(ratio))
+    }
+
+    fn mirror_lightness(&self, mirror: bool) -> Self {
+        self.with(|config| config.mirror_lightness = mirror)
+    }
+
+    fn padding(&self, padding: f64) -> Self {
+        self.with(|config| config.padding = padding)
+    }
+
+    fn size(&self, size: u32) -> Self {
+        self.with(|config| {
+            config.width = size;
+            config.height = size;
+        })
+    }
+
+    fn width(&self, width: u32) -> Self {
+        self.with(|config| config.width = width)
+    }
+
+    fn height(&self, height: u32) -> Self {
+        self.with(|config| config.height = height)
+    }
+
+    fn alignment(&self, alignment: &str) -> PyResult<Self> {
+        let alignment = parse_alignment(alignment)?;
+        Ok(self.with(|config| config.alignment = alignment))
+    }
+
+    fn antialiasing(&self, antialiasing: &str) -> PyResult<Self> {
+        let antialiasing = parse_antialiasing(antialiasing)?;
+        Ok(self.with(|config| config.antialiasing = antialiasing))
+    }
+
+    fn grid_size(&self, grid_size: u32) -> Self {
+        self.with(|config| config.grid_size = grid_size)
+    }
+
+    fn jdenticon_compat(&self, jdenticon_compat: bool) -> Self {
+        self.with(|config| config.jdenticon_compat = jdenticon_compat)
+    }
+
+    /// Builds the `Config`. Raises `ValueError` if the configuration is invalid.
+    fn build(&self) -> PyResult<PyConfig> {
+        self.0.validate().map_err(value_error)?;
+        Ok(PyConfig(self.0.clone()))
+    }
+}
+
+/// An identicon rendered as RGBA pixels, row by row.
+///
+/// This implements the NumPy array interface, so `numpy.asarray(image)` returns a
+/// `(height, width, 4)` array of `uint8` without copying.
+#[pyclass(module = "rdenticon", frozen)]
+struct RgbaImage {
+    #[pyo3(get)]
+    width: u32,
+    #[pyo3(get)]
+    height: u32,
+    #[pyo3(get)]
+    data: Py<PyBytes>,
+}
+
+#[pymethods]
+impl RgbaImage {
+    #[getter]
+    fn __array_interface__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let interface = PyDict::new(py);
+        interface.set_item("shape", (self.height, self.width, 4))?;
+        interface.set_item("typestr", "|u1")?;
+        interface.set_item("data", self.data.bind(py))?;
+        interface.set_item("version", 3)?;
+
+        Ok(interface)
+    }
+
+    fn tobytes(&self, py: Python<'_>) -> Py<PyBytes> {
+        self.data.clone_ref(py)
+    }
+
+    fn __bytes__(&self, py: Python<'_>) -> Py<PyBytes> {
+        self.data.clone_ref(py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("RgbaImage(width={}, height={})", self.width, self.height)
+    }
+}
+
+/// Renders `hash` in the given format, releasing the GIL while rendering.
+fn render(py: Python<'_>, hash: [u8; 20], config: &Config, format: &str) -> PyResult<PyObject> {
+    match format {
+        "png" | "rgba" => {
+            let image = py
+                .allow_threads(|| try_render_identicon(hash, config))
+                .map_err(value_error)?;
+
+            if format == "png" {
+                let mut png = Vec::new();
+                image
+                    .encode(ImageFormat::Png, &mut png)
+                    .map_err(value_error)?;
+                return Ok(PyBytes::new(py, &png).into_any().unbind());
+            }
+
+            let data = image
+                .data
+                .iter()
+                .flat_map(|Rgba { r, g, b, a }| [*r, *g, *b, *a])
+                .collect::<Vec<_>>();
+            let image = RgbaImage {
+                width: image.width(),
+                height: image.height(),
+                data: PyBytes::new(py, &data).unbind(),
+            };
+            Ok(Py::new(py, image)?.into_any())
+        }
+        "svg" => {
+            config.validate().map_err(value_error)?;
+            let svg = py.allow_threads(|| render_identicon_svg(hash, config));
+            Ok(svg.into_pyobject(py)?.into_any().unbind())
+        }
+        _ => Err(PyValueError::new_err(format!(
+            "unknown format {format:?}, expected \"png\", \"svg\" or \"rgba\""
+        ))),
+    }
+}
+
+/// Generates an identicon for the given `str`, `bytes` or `int` message, hashed with SHA-1.
+///
+/// `format` is one of `"png"` (returns `bytes`), `"svg"` (returns `str`) or `"rgba"` (returns
+/// an `RgbaImage`). Raises `ValueError` if the configuration is invalid.
+#[pyfunction]
+#[pyo3(signature = (message, config = None, format = "png"))]
+fn generate_identicon(
+    py: Python<'_>,
+    message: Message,
+    config: Option<&PyConfig>,
+    format: &str,
+) -> PyResult<PyObject> {
+    let default = Config::default();
+    render(
+        py,
+        message.hash(),
+        config.map_or(&default, |c| &c.0),
+        format,
+    )
+}
+
+/// Renders an identicon for the given hash. Hashes that are not 20 bytes long are reduced like
+/// `reduce_digest` does, and must be at least 6 bytes long.
+///
+/// See `generate_identicon` for the supported formats. Raises `ValueError` if the hash is too
+/// short or the configuration is invalid.
+#[pyfunction]
+#[pyo3(signature = (hash, config = None, format = "png"))]
+fn render_identicon(
+    py: Python<'_>,
+    hash: &[u8],
+    config: Option<&PyConfig>,
+    format: &str,
+) -> PyResult<PyObject> {
+    let hash = match hash.len() {
+        20 => hash.try_into().expect("hash is 20 bytes long"),
+        6.. => reduce_digest(hash),
+        _ => return Err(value_error("hash must be at least 6 bytes long")),
+    };
+
+    let default = Config::default();
+    render(py, hash, config.map_or(&default, |c| &c.0), format)
+}
+
+/// Describes the identicon for the given `str`, `bytes` or `int` message in English, for example
+/// as alt text for screen readers. Raises `ValueError` if the configuration is invalid.
+#[pyfunction]
+#[pyo3(signature = (message, config = None))]
+fn describe_identicon(message: Message, config: Option<&PyConfig>) -> PyResult<String> {
+    let default = Config::default();
+    let config = config.map_or(&default, |c| &c.0);
+    config.validate().map_err(value_error)?;
+
+    Ok(describe(message.hash(), config))
+}
+
+#[pymodule]
+#[pyo3(name = "rdenticon")]
+fn rdenticon_python(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PyConfigBuilder>()?;
+    m.add_class::<RgbaImage>()?;
+    m.add_function(wrap_pyfunction!(generate_identicon, m)?)?;
+    m.add_function(wrap_pyfunction!(render_identicon, m)?)?;
+    m.add_function(wrap_pyfunction!(describe_identicon, m)?)?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+import pytest
+
+import rdenticon
+
+
+def test_generate_identicon():
+    config = rdenticon.Config(size=64, hues=[120.0], background_color=(255, 255, 255))
+
+    png = rdenticon.generate_identicon("rdenticon", config)
+    assert isinstance(png, bytes)
+
+    svg = rdenticon.generate_identicon("rdenticon", config, format="svg")
+    assert svg.startswith("<svg")
+
+    image = rdenticon.generate_identicon(b"rdenticon", config, format="rgba")
+    assert (image.width, image.height) == (64, 64)
+    assert len(image.tobytes()) == 64 * 64 * 4
+    assert image.__array_interface__["shape"] == (64, 64, 4)
+
+
+def test_render_identicon():
+    config = rdenticon.Config.builder().size(32).build()
+    svg = rdenticon.render_identicon(bytes(range(20)), config, format="svg")
+    assert 'width="32"' in svg
+
+    with pytest.raises(ValueError):
+        rdenticon.render_identicon(b"short", config)
+
+
+def test_invalid_config():
+    with pytest.raises(ValueError, match="padding must be within the range"):
+        rdenticon.Config(padding=0.75)
+
+    with pytest.raises(ValueError, match="hues must be within the range"):
+        rdenticon.Config.builder().hues([360.0]).build()
+
+
+def test_non_square():
+    config = rdenticon.Config(width=96, height=48, alignment="tile")
+    assert (config.width, config.height, config.alignment) == (96, 48, "tile")
+
+    image = rdenticon.generate_identicon("rdenticon", config, format="rgba")
+    assert image.__array_interface__["shape"] == (48, 96, 4)
+
+    with pytest.raises(ValueError, match="unknown alignment"):
+        rdenticon.Config.builder().alignment("stretch")
+
+
+def test_palettes():
+    palette = {
+        "dark_gray": (17, 17, 17),
+        "mid_color": (136, 136, 136),
+        "light_gray": (238, 238, 238),
+        "light_color": (136, 204, 255),
+        "dark_color": (0, 68, 136, 255),
+    }
+    config = rdenticon.Config(palettes=[palette], background_color=(0, 0, 0, 0))
+    assert config.palettes[0]["light_color"] == (136, 204, 255, 255)
+
+    svg = rdenticon.generate_identicon("rdenticon", config, format="svg")
+    fills = {fill[:7] for fill in svg.split('fill="')[1:]}
+    assert fills <= {"#111111", "#888888", "#eeeeee", "#88ccff", "#004488"}
+
+
+def test_color_space():
+    config = rdenticon.Config(color_space="oklch")
+    assert config.color_space == "oklch"
+    assert rdenticon.ConfigBuilder().color_space("cielch").build().color_space == "cielch"
+    assert rdenticon.generate_identicon("rdenticon", config, format="svg").startswith("<svg")
+
+    with pytest.raises(ValueError, match="unknown color space"):
+        rdenticon.Config(color_space="hsv")
+
+
+def test_antialiasing():
+    config = rdenticon.Config(size=32, antialiasing="lanczos3:4")
+    assert config.antialiasing == "lanczos3:4"
+    assert rdenticon.ConfigBuilder().antialiasing("coverage").build().antialiasing == "coverage"
+
+    image = rdenticon.generate_identicon("rdenticon", config, format="rgba")
+    assert len(image.tobytes()) == 32 * 32 * 4
+
+    with pytest.raises(ValueError, match="supersampling factor must be within the range"):
+        rdenticon.Config(antialiasing="box:32")
+
+
+def test_describe_identicon():
+    config = rdenticon.Config(hues=[180])
+    description = rdenticon.describe_identicon("rdenticon", config)
+    assert description.startswith("teal identicon with ")
+    assert description == rdenticon.describe_identicon("rdenticon", config)
@@ -0,0 +1,354 @@
+use crate::{
+    render_identicon_with,
+    renderer::{ellipse_points, StretchRenderer},
+    Antialiasing, BufferRenderer, Config, DownscaleFilter, Renderer, Rgba,
+};
+
+/// A [`Renderer`] that draws anti-aliased shapes onto a caller-provided buffer of RGBA8 pixels.
+///
+/// Rows are `stride` bytes apart, and each pixel is 4 bytes in RGBA order, like
+/// [`BufferRenderer`]. Each pixel is blended with the color of a shape by the exact fraction of
+/// its area that the shape covers, so edges are smooth and shapes that share an edge meet without
+/// a seam. Ellipses are approximated by polygons.
+///
+/// The shapes drawn between [`Renderer::begin_shape`] and [`Renderer::end_shape`] are blended
+/// onto the buffer all at once when the shape ends, so that the shapes of a group do not blend
+/// with each other along the edges they share. Inverted shapes cut holes into the shapes of the
+/// group drawn before them.
+pub struct CoverageRenderer<'a> {
+    buffer: &'a mut [u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    color: Rgba,
+    /// The coverage of each pixel by the shapes of the current group, within `[0.0, 1.0]`.
+    coverage: Vec<f32>,
+    /// The signed area accumulated by the edges of the polygon being drawn. This has two extra
+    /// entries, since edges at the right of the last row spill over past its end.
+    area: Vec<f32>,
+    /// The rows touched by the current group.
+    dirty: std::ops::Range<usize>,
+}
+
+impl<'a> CoverageRenderer<'a> {
+    /// Creates a new renderer that draws onto the `width` by `height` pixels at the start of the
+    /// given buffer.
+    ///
+    /// # Panics
+    /// * If `stride` is less than `width * 4`.
+    /// * If the buffer is shorter than `stride * (height - 1) + width * 4` bytes.
+    pub fn new(buffer: &'a mut [u8], stride: usize, width: u32, height: u32) -> Self {
+        let (width, height) = (width as usize, height as usize);
+        assert!(stride >= width * 4, "stride must be at least width * 4");
+        assert!(
+            height == 0 || buffer.len() >= stride * (height - 1) + width * 4,
+            "buffer is too small",
+        );
+
+        Self {
+            buffer,
+            stride,
+            width,
+            height,
+            color: Rgba::transparent(),
+            coverage: vec![0.0; width * height],
+            area: vec![0.0; width * height + 2],
+            dirty: 0..0,
+        }
+    }
+
+    /// Accumulates the signed area to the right of an edge into each row it crosses. This is the
+    /// accumulation rasterizer of font-rs, with `x` clamped to the width of the buffer.
+    fn line(&mut self, (x0, y0): (f64, f64), (x1, y1): (f64, f64)) {
+        let width = self.width as f32;
+        let clamp = |x: f64| (x as f32).clamp(0.0, width);
+        let (p0, p1) = ((clamp(x0), y0 as f32), (clamp(x1), y1 as f32));
+        #[allow(clippy::float_cmp)]
+        if p0.1 == p1.1 {
+            return;
+        }
+
+        let (direction, p0, p1) = if p0.1 < p1.1 {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+        let dxdy = (p1.0 - p0.0) / (p1.1 - p0.1);
+        let mut x = if p0.1 < 0.0 { p0.0 - p0.1 * dxdy } else { p0.0 };
+
+        let y_start = p0.1.max(0.0) as usize;
+        let y_end = (p1.1.ceil().max(0.0) as usize).min(self.height);
+        for y in y_start..y_end {
+            let row = y * self.width;
+            let dy = ((y + 1) as f32).min(p1.1) - (y as f32).max(p0.1);
+            let x_next = dxdy.mul_add(dy, x).clamp(0.0, width);
+            let d = dy * direction;
+
+            let (x_left, x_right) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let left_floor = x_left.floor();
+            let left = left_floor as usize;
+            let right_ceil = x_right.ceil();
+            let right = right_ceil as usize;
+
+            if right <= left + 1 {
+                // The edge stays within one pixel of this row
+                let mid = 0.5f32.mul_add(x + x_next, -left_floor);
+                self.area[row + left] += d - d * mid;
+                self.area[row + left + 1] += d * mid;
+            } else {
+                let slope = (x_right - x_left).recip();
+                let left_fract = x_left - left_floor;
+                let a0 = 0.5 * slope * (1.0 - left_fract) * (1.0 - left_fract);
+                let right_fract = x_right - right_ceil + 1.0;
+                let am = 0.5 * slope * right_fract * right_fract;
+
+                self.area[row + left] += d * a0;
+                if right == left + 2 {
+                    self.area[row + left + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = slope * (1.5 - left_fract);
+                    self.area[row + left + 1] += d * (a1 - a0);
+                    for area in &mut self.area[row + left + 2..row + right - 1] {
+                        *area += d * slope;
+                    }
+                    let a2 = ((right - left - 3) as f32).mul_add(slope, a1);
+                    self.area[row + right - 1] += d * (1.0 - a2 - am);
+                }
+                self.area[row + right] += d * am;
+            }
+
+            x = x_next;
+        }
+    }
+}
+
+/// Blends `color` onto an RGBA8 pixel with the given coverage, treating both as straight alpha.
+fn blend(pixel: &mut [u8], color: Rgba, coverage: f32) {
+    let source_alpha = f32::from(color.a) / 255.0 * coverage;
+    let dest_alpha = f32::from(pixel[3]) / 255.0;
+    let alpha = dest_alpha.mul_add(1.0 - source_alpha, source_alpha);
+    if alpha <= 0.0 {
+        return;
+    }
+
+    for (channel, source) in pixel[..3].iter_mut().zip([color.r, color.g, color.b]) {
+        let value = f32::from(source) * source_alpha
+            + f32::from(*channel) * dest_alpha * (1.0 - source_alpha);
+        *channel = (value / alpha).round() as u8;
+    }
+    pixel[3] = (alpha * 255.0).round() as u8;
+}
+
+impl Renderer for CoverageRenderer<'_> {
+    fn background(&mut self, color: Rgba) {
+        for y in 0..self.height {
+            let row = y * self.stride;
+            for pixel in self.buffer[row..row + self.width * 4].chunks_exact_mut(4) {
+                pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+            }
+        }
+    }
+
+    fn begin_shape(&mut self, color: Rgba) {
+        self.color = color;
+    }
+
+    fn end_shape(&mut self) {
+        for y in self.dirty.clone() {
+            let coverage = &mut self.coverage[y * self.width..(y + 1) * self.width];
+            let row = &mut self.buffer[y * self.stride..y * self.stride + self.width * 4];
+
+            for (coverage, pixel) in coverage.iter_mut().zip(row.chunks_exact_mut(4)) {
+                // Less coverage than this cannot change a pixel, and is mostly rounding error
+                if *coverage >= 1.0 / 512.0 {
+                    blend(pixel, self.color, *coverage);
+                }
+                *coverage = 0.0;
+            }
+        }
+        self.dirty = 0..0;
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool) {
+        let (y_min, y_max) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, y)| {
+                (min.min(y), max.max(y))
+            });
+        let end = (y_max.ceil().max(0.0) as usize).min(self.height);
+        let start = (y_min.floor().max(0.0) as usize).min(end);
+        if start == end {
+            return;
+        }
+
+        for (i, &point) in points.iter().enumerate() {
+            self.line(point, points[(i + 1) % points.len()]);
+        }
+
+        // The running sum of the signed area is the coverage of each pixel by the polygon
+        let mut sum = 0.0f32;
+        let range = start * self.width..end * self.width;
+        for (area, coverage) in self.area[range.clone()]
+            .iter_mut()
+            .zip(&mut self.coverage[range.clone()])
+        {
+            sum += *area;
+            *area = 0.0;
+
+            let polygon = sum.abs().min(1.0);
+            *coverage = if invert {
+                (*coverage - polygon).max(0.0)
+            } else {
+                (*coverage + polygon).min(1.0)
+            };
+        }
+        self.area[range.end..range.end + 2].fill(0.0);
+
+        self.dirty = if self.dirty.is_empty() {
+            start..end
+        } else {
+            self.dirty.start.min(start)..self.dirty.end.max(end)
+        };
+    }
+
+    fn circle(&mut self, top_left: (f64, f64), diameter: f64, invert: bool) {
+        self.ellipse(top_left, (diameter, diameter), invert);
+    }
+
+    fn ellipse(&mut self, top_left: (f64, f64), size: (f64, f64), invert: bool) {
+        self.polygon(&ellipse_points(top_left, size), invert);
+    }
+}
+
+/// Returns the weights of the source samples of each destination pixel along an axis, as the
+/// index of the first sample and the weights of the samples from it onwards.
+fn filter_weights(len: usize, factor: usize, filter: DownscaleFilter) -> Vec<(usize, Vec<f32>)> {
+    let lanczos3 = |x: f64| {
+        if x == 0.0 {
+            1.0
+        } else if x.abs() < 3.0 {
+            let x = x * std::f64::consts::PI;
+            3.0 * x.sin() * (x / 3.0).sin() / (x * x)
+        } else {
+            0.0
+        }
+    };
+
+    (0..len)
+        .map(|i| match filter {
+            DownscaleFilter::Box => (i * factor, vec![1.0 / factor as f32; factor]),
+            DownscaleFilter::Lanczos3 => {
+                let center = (i as f64 + 0.5) * factor as f64;
+                let radius = 3.0 * factor as f64;
+                let start = (center - radius).floor().max(0.0) as usize;
+                let end = ((center + radius).ceil() as usize).min(len * factor);
+
+                // Weights are normalized, since samples past the edges are left out
+                let mut weights = (start..end)
+                    .map(|sample| lanczos3((sample as f64 + 0.5 - center) / factor as f64))
+                    .collect::<Vec<_>>();
+                let sum = weights.iter().sum::<f64>();
+                weights.iter_mut().for_each(|weight| *weight /= sum);
+                (
+                    start,
+                    weights.into_iter().map(|weight| weight as f32).collect(),
+                )
+            }
+        })
+        .collect()
+}
+
+/// Downscales a tightly packed `width * factor` by `height * factor` RGBA8 image into the
+/// `width` by `height` pixels at the start of `buffer`, filtering with premultiplied alpha.
+fn downscale(
+    source: &[u8],
+    factor: usize,
+    filter: DownscaleFilter,
+    buffer: &mut [u8],
+    stride: usize,
+    (width, height): (usize, usize),
+) {
+    let horizontal = filter_weights(width, factor, filter);
+    let vertical = filter_weights(height, factor, filter);
+    let source_width = width * factor;
+
+    // Filter each row of the source horizontally first, then filter the result vertically
+    let mut rows = vec![[0.0f32; 4]; width * height * factor];
+    for (y, row) in rows.chunks_exact_mut(width).enumerate() {
+        let source = &source[y * source_width * 4..(y + 1) * source_width * 4];
+        for ((start, weights), out) in horizontal.iter().zip(row) {
+            for (pixel, weight) in source[start * 4..].chunks_exact(4).zip(weights) {
+                let alpha = f32::from(pixel[3]) / 255.0;
+                out[0] += weight * f32::from(pixel[0]) * alpha;
+                out[1] += weight * f32::from(pixel[1]) * alpha;
+                out[2] += weight * f32::from(pixel[2]) * alpha;
+                out[3] += weight * f32::from(pixel[3]);
+            }
+        }
+    }
+
+    for (y, (start, weights)) in vertical.iter().enumerate() {
+        let row = &mut buffer[y * stride..y * stride + width * 4];
+        for (x, pixel) in row.chunks_exact_mut(4).enumerate() {
+            let mut sum = [0.0f32; 4];
+            for (i, weight) in weights.iter().enumerate() {
+                let sample = rows[(start + i) * width + x];
+                for (sum, sample) in sum.iter_mut().zip(sample) {
+                    *sum += weight * sample;
+                }
+            }
+
+            let alpha = sum[3].clamp(0.0, 255.0);
+            if alpha == 0.0 {
+                pixel.copy_from_slice(&[0; 4]);
+                continue;
+            }
+            for (channel, sum) in pixel.iter_mut().zip(&sum[..3]) {
+                *channel = (sum / alpha * 255.0).round().clamp(0.0, 255.0) as u8;
+            }
+            pixel[3] = alpha.round() as u8;
+        }
+    }
+}
+
+/// Renders an identicon into a buffer of RGBA8 pixels with the anti-aliasing configured by
+/// [`Config::antialiasing`]. The configuration and the buffer must already be validated.
+pub(crate) fn render_antialiased(
+    hash: [u8; 20],
+    config: &Config,
+    buffer: &mut [u8],
+    stride: usize,
+) {
+    let (width, height) = (config.width, config.height);
+    match config.antialiasing {
+        Antialiasing::None => {
+            let mut renderer = BufferRenderer::new(buffer, stride, width, height);
+            render_identicon_with(hash, config, &mut renderer);
+        }
+        Antialiasing::Coverage => {
+            let mut renderer = CoverageRenderer::new(buffer, stride, width, height);
+            render_identicon_with(hash, config, &mut renderer);
+        }
+        Antialiasing::Supersample { factor, filter } => {
+            // The icon is laid out at its own size and only drawn scaled up, so that shapes are
+            // where they would be without supersampling. `Config::validate` ensures that the
+            // scaled up size does not overflow
+            let (large_width, large_height) = (width * factor, height * factor);
+            let mut source = vec![0; large_width as usize * large_height as usize * 4];
+            let mut renderer = StretchRenderer {
+                renderer: &mut BufferRenderer::new(
+                    &mut source,
+                    large_width as usize * 4,
+                    large_width,
+                    large_height,
+                ),
+                origin: (0.0, 0.0),
+                scale: (f64::from(factor), f64::from(factor)),
+            };
+            render_identicon_with(hash, config, &mut renderer);
+
+            let size = (width as usize, height as usize);
+            downscale(&source, factor as usize, filter, buffer, stride, size);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use crate::{
+    draw_identicon, generate_identicon, reduce_digest, Config, IdenticonHasher, IdenticonInput,
+    RenderError, Sha1,
+};
+use rayon::prelude::*;
+use ril::prelude::*;
+
+/// Validates the configuration once for a whole batch, like
+/// [`try_render_identicon`](crate::try_render_identicon) does for a single icon.
+fn validate(config: &Config) -> Result<(), RenderError> {
+    config.validate()?;
+    if config.width == 0 || config.height == 0 {
+        return Err(RenderError::ZeroSize);
+    }
+
+    Ok(())
+}
+
+/// Generates identicons for many inputs in parallel, hashing them with SHA-1 like
+/// [`generate_identicon`]. The returned images are in the same order as the inputs, and have the
+/// same pixels as those of [`generate_identicon`].
+///
+/// This requires the `rayon` feature. Work is spread over rayon's global thread pool, so it can
+/// be configured with [`rayon::ThreadPoolBuilder`] or run within a custom pool with
+/// [`rayon::ThreadPool::install`].
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero.
+pub fn render_batch<I: IdenticonInput + Sync>(
+    inputs: &[I],
+    config: &Config,
+) -> Result<Vec<Image<Rgba>>, RenderError> {
+    validate(config)?;
+
+    Ok(inputs
+        .par_iter()
+        .map(|input| generate_identicon(input, config))
+        .collect())
+}
+
+/// Generates identicons for many inputs in parallel and passes each of them to `callback` along
+/// with the index of its input, without keeping them all in memory. Inputs are hashed with SHA-1
+/// like [`generate_identicon`], and have the same pixels.
+///
+/// Each thread renders into its own image, which is reused for every identicon it renders, so the
+/// image passed to `callback` is only valid for the duration of the call. Callbacks are called
+/// from multiple threads and in no particular order.
+///
+/// This requires the `rayon` feature.
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero.
+pub fn render_batch_streaming<I, F>(
+    inputs: &[I],
+    config: &Config,
+    callback: F,
+) -> Result<(), RenderError>
+where
+    I: IdenticonInput + Sync,
+    F: Fn(usize, &Image<Rgba>) + Sync + Send,
+{
+    validate(config)?;
+
+    inputs.par_iter().enumerate().for_each_init(
+        || Image::new(config.width, config.height, config.background_color),
+        |image, (index, input)| {
+            let hash = reduce_digest(Sha1::digest(&input.identicon_bytes()).as_ref());
+            draw_identicon(hash, config, image, (0, 0));
+            callback(index, image);
+        },
+    );
+    Ok(())
+}
@@ -0,0 +1,388 @@
+use clap::{Parser, ValueEnum};
+use rdenticon::{
+    parse_hex_color, reduce_digest, render_identicon, render_identicon_svg,
+    render_identicon_themed_svg, Alignment, Antialiasing, ColorSpace, Config, DownscaleFilter,
+    IdenticonHasher, ImageFormat, Palette, Rgba, Sha1, Theme, ThemedConfig,
+};
+use std::{
+    error::Error,
+    fs,
+    io::{self, BufRead, Write},
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+/// Generates identicons from messages or hashes.
+#[derive(Parser)]
+#[command(name = "rdenticon", version, about)]
+struct Args {
+    /// The message to generate an identicon for, or a hex-encoded hash when `--hash` is given.
+    #[arg(required_unless_present = "batch", conflicts_with = "batch")]
+    value: Option<String>,
+
+    /// Treat values as hex-encoded hashes rather than messages to hash with SHA-1.
+    #[arg(long)]
+    hash: bool,
+
+    /// The file to write the identicon to. Writes to stdout if omitted or `-`.
+    #[arg(short, long, conflicts_with = "batch")]
+    output: Option<PathBuf>,
+
+    /// The output format. Inferred from the output file extension if omitted, defaulting to PNG.
+    #[arg(short, long, value_enum)]
+    format: Option<Format>,
+
+    /// Read one value per line from stdin and write one identicon per line to this directory.
+    /// Files are named after their 1-based line number, e.g. `1.png`.
+    #[arg(long, value_name = "DIR")]
+    batch: Option<PathBuf>,
+
+    /// The width and height of the icon in pixels.
+    #[arg(short, long)]
+    size: Option<u32>,
+
+    /// The width of the icon in pixels, overriding `--size`.
+    #[arg(long)]
+    width: Option<u32>,
+
+    /// The height of the icon in pixels, overriding `--size`.
+    #[arg(long)]
+    height: Option<u32>,
+
+    /// How the icon's grid is placed within non-square icons.
+    #[arg(long, value_enum)]
+    alignment: Option<AlignmentArg>,
+
+    /// How shapes are anti-aliased in PNG output: `none`, `coverage` for exact area coverage, or
+    /// a downscale filter and a supersampling factor, such as `box:4` or `lanczos3:4`.
+    #[arg(long, value_name = "MODE", value_parser = parse_antialiasing)]
+    antialiasing: Option<Antialiasing>,
+
+    /// The padding surrounding the icon relative to its size, within `[0.0, 0.5]`.
+    #[arg(short, long)]
+    padding: Option<f64>,
+
+    /// A comma-separated list of hues in degrees to limit the identicon to.
+    #[arg(long, value_delimiter = ',')]
+    hues: Vec<f64>,
+
+    /// The lightness range of colored shapes, as `MIN,MAX`.
+    #[arg(long, value_name = "MIN,MAX", value_parser = parse_range)]
+    color_lightness: Option<RangeInclusive<f64>>,
+
+    /// The lightness range of grayscale shapes, as `MIN,MAX`.
+    #[arg(long, value_name = "MIN,MAX", value_parser = parse_range)]
+    grayscale_lightness: Option<RangeInclusive<f64>>,
+
+    /// The saturation of colored shapes, within `[0.0, 1.0]`.
+    #[arg(long)]
+    color_saturation: Option<f64>,
+
+    /// The saturation of grayscale shapes, within `[0.0, 1.0]`.
+    #[arg(long)]
+    grayscale_saturation: Option<f64>,
+
+    /// The color space that colors are derived from a hue in.
+    #[arg(long, value_enum)]
+    color_space: Option<ColorSpaceArg>,
+
+    /// A fixed palette to use instead of deriving colors from a hue, as the comma-separated
+    /// dark gray, mid color, light gray, light color and dark color. Can be repeated, in which
+    /// case the hash picks one of the palettes.
+    #[arg(long = "palette", value_name = "COLORS", value_parser = parse_palette)]
+    palettes: Vec<Palette>,
+
+    /// The background color, as `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa`.
+    #[arg(long, value_name = "COLOR", value_parser = parse_color)]
+    background_color: Option<Rgba>,
+
+    /// The minimum WCAG contrast ratio of the icon's colors against the background color, within
+    /// `[1.0, 21.0]`. Colors below it are lightened or darkened until they reach it.
+    #[arg(long, value_name = "RATIO")]
+    min_contrast: Option<f64>,
+
+    /// The color scheme to render. The dark variant mirrors the lightness of the configured colors
+    /// and background. `auto` renders both into one SVG that follows the viewer's color scheme.
+    #[arg(long, value_enum, default_value = "light")]
+    theme: ThemeArg,
+
+    /// The amount of cells along each side of the icon's grid, within `4..=10`.
+    #[arg(long)]
+    grid_size: Option<u32>,
+
+    /// Strictly follow jdenticon's geometry and color conversion.
+    #[arg(long)]
+    jdenticon_compat: bool,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum AlignmentArg {
+    Center,
+    Fit,
+    Tile,
+}
+
+impl From<AlignmentArg> for Alignment {
+    fn from(alignment: AlignmentArg) -> Self {
+        match alignment {
+            AlignmentArg::Center => Self::Center,
+            AlignmentArg::Fit => Self::Fit,
+            AlignmentArg::Tile => Self::Tile,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ColorSpaceArg {
+    Hsl,
+    Oklch,
+    Cielch,
+}
+
+impl From<ColorSpaceArg> for ColorSpace {
+    fn from(color_space: ColorSpaceArg) -> Self {
+        match color_space {
+            ColorSpaceArg::Hsl => Self::Hsl,
+            ColorSpaceArg::Oklch => Self::Oklch,
+            ColorSpaceArg::Cielch => Self::Cielch,
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum ThemeArg {
+    Light,
+    Dark,
+    Auto,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Png,
+    Svg,
+}
+
+impl Format {
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+}
+
+fn parse_range(s: &str) -> Result<RangeInclusive<f64>, String> {
+    let (start, end) = s.split_once(',').ok_or("expected MIN,MAX")?;
+    let start = start.trim().parse::<f64>().map_err(|err| err.to_string())?;
+    let end = end.trim().parse::<f64>().map_err(|err| err.to_string())?;
+
+    Ok(start..=end)
+}
+
+fn parse_color(s: &str) -> Result<Rgba, String> {
+    parse_hex_color(s).ok_or_else(|| "expected #rgb, #rgba, #rrggbb or #rrggbbaa".to_string())
+}
+
+fn parse_antialiasing(s: &str) -> Result<Antialiasing, String> {
+    let supersample = |factor: &str, filter| {
+        let factor = factor
+            .parse()
+            .map_err(|_| format!("invalid factor {factor:?}"))?;
+        Ok(Antialiasing::Supersample { factor, filter })
+    };
+
+    match s.split_once(':') {
+        None if s == "none" => Ok(Antialiasing::None),
+        None if s == "coverage" => Ok(Antialiasing::Coverage),
+        Some(("box", factor)) => supersample(factor, DownscaleFilter::Box),
+        Some(("lanczos3", factor)) => supersample(factor, DownscaleFilter::Lanczos3),
+        _ => Err("expected none, coverage, box:N or lanczos3:N".to_string()),
+    }
+}
+
+fn parse_palette(s: &str) -> Result<Palette, String> {
+    let colors = s
+        .split(',')
+        .map(|color| parse_color(color.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [dark_gray, mid_color, light_gray, light_color, dark_color] = colors[..] else {
+        return Err("expected 5 comma-separated colors".to_string());
+    };
+    Ok(Palette {
+        dark_gray,
+        mid_color,
+        light_gray,
+        light_color,
+        dark_color,
+    })
+}
+
+fn parse_hash(s: &str) -> Result<[u8; 20], String> {
+    let s = s.trim();
+    if s.len() & 1 != 0 || !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!("{s:?} is not a hex-encoded hash"));
+    }
+
+    let bytes: Vec<u8> = (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+        .collect::<Result<_, _>>()
+        .expect("validated as hex above");
+
+    match bytes.len() {
+        20 => Ok(bytes.try_into().unwrap()),
+        6.. => Ok(reduce_digest(&bytes)),
+        _ => Err(format!(
+            "{s:?} is too short, hashes must be at least 6 bytes"
+        )),
+    }
+}
+
+impl Args {
+    fn config(&self) -> Result<Config, Box<dyn Error>> {
+        let default = Config::default();
+        let config = Config {
+            hues: self.hues.clone(),
+            color_lightness: self
+                .color_lightness
+                .clone()
+                .unwrap_or(default.color_lightness),
+            grayscale_lightness: self
+                .grayscale_lightness
+                .clone()
+                .unwrap_or(default.grayscale_lightness),
+            color_saturation: self.color_saturation.unwrap_or(default.color_saturation),
+            grayscale_saturation: self
+                .grayscale_saturation
+                .unwrap_or(default.grayscale_saturation),
+            color_space: self
+                .color_space
+                .map_or(default.color_space, ColorSpace::from),
+            palettes: self.palettes.clone(),
+            background_color: self.background_color.unwrap_or(default.background_color),
+            min_contrast: self.min_contrast,
+            // The dark variant of `--theme` is what mirrors lightness
+            mirror_lightness: default.mirror_lightness,
+            padding: self.padding.unwrap_or(default.padding),
+            width: self.width.or(self.size).unwrap_or(default.width),
+            height: self.height.or(self.size).unwrap_or(default.height),
+            alignment: self.alignment.map_or(default.alignment, Alignment::from),
+            antialiasing: self.antialiasing.unwrap_or(default.antialiasing),
+            grid_size: self.grid_size.unwrap_or(default.grid_size),
+            shapes: default.shapes,
+            jdenticon_compat: self.jdenticon_compat,
+        };
+        config.validate()?;
+
+        if config.width == 0 || config.height == 0 {
+            return Err("width and height must be greater than 0".into());
+        }
+        Ok(config)
+    }
+
+    fn hash(&self, value: &str) -> Result<[u8; 20], Box<dyn Error>> {
+        if self.hash {
+            Ok(parse_hash(value)?)
+        } else {
+            Ok(reduce_digest(Sha1::digest(value.as_bytes()).as_ref()))
+        }
+    }
+
+    fn format(&self) -> Format {
+        self.format
+            .unwrap_or_else(|| match self.output.as_deref().and_then(Path::extension) {
+                Some(ext) if ext.eq_ignore_ascii_case("svg") => Format::Svg,
+                _ => Format::Png,
+            })
+    }
+}
+
+/// Writes an identicon in the given format. Without a theme, both variants are written into one
+/// SVG document.
+fn write_identicon(
+    hash: [u8; 20],
+    config: &ThemedConfig,
+    theme: Option<Theme>,
+    format: Format,
+    dest: &mut impl Write,
+) -> Result<(), Box<dyn Error>> {
+    match (format, theme) {
+        (Format::Png, Some(theme)) => {
+            render_identicon(hash, config.get(theme)).encode(ImageFormat::Png, dest)?;
+        }
+        (Format::Svg, Some(theme)) => {
+            dest.write_all(render_identicon_svg(hash, config.get(theme)).as_bytes())?;
+        }
+        (Format::Svg, None) => {
+            dest.write_all(render_identicon_themed_svg(hash, config)?.as_bytes())?;
+        }
+        (Format::Png, None) => return Err("--theme auto requires SVG output".into()),
+    }
+    Ok(())
+}
+
+fn run(args: &Args) -> Result<(), Box<dyn Error>> {
+    let config = ThemedConfig::from_light(args.config()?);
+    let theme = match args.theme {
+        ThemeArg::Light => Some(Theme::Light),
+        ThemeArg::Dark => Some(Theme::Dark),
+        ThemeArg::Auto => None,
+    };
+    let format = args.format();
+
+    if let Some(dir) = &args.batch {
+        fs::create_dir_all(dir)?;
+
+        for (i, line) in io::stdin().lock().lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+
+            let hash = args
+                .hash(line)
+                .map_err(|err| format!("line {}: {err}", i + 1))?;
+            let path = dir.join(format!("{}.{}", i + 1, format.extension()));
+            let mut file = io::BufWriter::new(fs::File::create(&path)?);
+
+            write_identicon(hash, &config, theme, format, &mut file)?;
+            file.flush()?;
+        }
+        return Ok(());
+    }
+
+    let value = args
+        .value
+        .as_deref()
+        .expect("value is required without --batch");
+    let hash = args.hash(value)?;
+
+    match args.output.as_deref() {
+        Some(path) if path != Path::new("-") => {
+            let mut file = io::BufWriter::new(fs::File::create(path)?);
+            write_identicon(hash, &config, theme, format, &mut file)?;
+            file.flush()?;
+        }
+        _ => {
+            let mut stdout = io::stdout().lock();
+            write_identicon(hash, &config, theme, format, &mut stdout)?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("error: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,828 @@
+//! C bindings, enabled by the `capi` feature.
+//!
+//! The library is only built as an `rlib` by default, so request a shared or static library
+//! explicitly when building the bindings:
+//!
+//! ```sh
+//! cargo rustc --lib --release --features capi --crate-type cdylib
+//! cargo rustc --lib --release --features capi --crate-type staticlib
+//! ```
+//!
+//! The header for these bindings is generated with cbindgen and lives at `include/rdenticon.h`.
+//! After changing this module, regenerate it with:
+//!
+//! ```sh
+//! cbindgen --config cbindgen.toml --output include/rdenticon.h
+//! ```
+//!
+//! Configurations are opaque handles created with [`rdenticon_config_new`] and freed with
+//! [`rdenticon_config_free`]. There is one setter per [`ConfigBuilder`](crate::ConfigBuilder)
+//! method, and [`rdenticon_config_build`] validates the configuration like
+//! [`ConfigBuilder::build`](crate::ConfigBuilder::build). Rendering functions validate the
+//! configuration as well, so calling [`rdenticon_config_build`] first is optional.
+//!
+//! Buffers and strings returned by rendering functions are owned by the caller and must be freed
+//! with [`rdenticon_rgba_free`] and [`rdenticon_string_free`] respectively.
+
+use crate::{
+    reduce_digest, render_identicon_into, render_identicon_svg, Alignment, Antialiasing,
+    ColorSpace, Config, ConfigBuilderError, DownscaleFilter, IdenticonHasher, Palette, RenderError,
+    Rgba, Sha1,
+};
+use std::{
+    ffi::{c_char, CStr, CString},
+    ptr, slice,
+};
+
+/// An opaque handle to a configuration.
+pub struct RdenticonConfig(Config);
+
+/// An enum that C passes as a plain integer, since a value outside of a Rust enum is undefined
+/// behavior.
+trait FromRaw: Copy + 'static {
+    /// Every variant, in the order of their discriminants starting from zero.
+    const VARIANTS: &'static [Self];
+
+    /// Converts an integer from C, or returns `None` if it is not one of the variants.
+    fn from_raw(value: u32) -> Option<Self> {
+        Self::VARIANTS.get(value as usize).copied()
+    }
+}
+
+/// The color space that colors are derived from a hue in. See `ColorSpace` for more information.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RdenticonColorSpace {
+    /// HSL, with lightness corrected for each hue the same way jdenticon does.
+    Hsl,
+    /// OKLCH, with perceptually uniform lightness.
+    Oklch,
+    /// CIELCh with a D65 white point.
+    Cielch,
+}
+
+impl FromRaw for RdenticonColorSpace {
+    const VARIANTS: &'static [Self] = &[Self::Hsl, Self::Oklch, Self::Cielch];
+}
+
+impl From<RdenticonColorSpace> for ColorSpace {
+    fn from(color_space: RdenticonColorSpace) -> Self {
+        match color_space {
+            RdenticonColorSpace::Hsl => Self::Hsl,
+            RdenticonColorSpace::Oklch => Self::Oklch,
+            RdenticonColorSpace::Cielch => Self::Cielch,
+        }
+    }
+}
+
+/// Fixed colors of an identicon, each as `{r, g, b, a}`. See `Palette` for more information.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct RdenticonPalette {
+    /// A dark grayscale color.
+    pub dark_gray: [u8; 4],
+    /// A color of medium lightness.
+    pub mid_color: [u8; 4],
+    /// A light grayscale color.
+    pub light_gray: [u8; 4],
+    /// A light color.
+    pub light_color: [u8; 4],
+    /// A dark color.
+    pub dark_color: [u8; 4],
+}
+
+impl From<RdenticonPalette> for Palette {
+    fn from(palette: RdenticonPalette) -> Self {
+        let color = |[r, g, b, a]: [u8; 4]| Rgba { r, g, b, a };
+        Self {
+            dark_gray: color(palette.dark_gray),
+            mid_color: color(palette.mid_color),
+            light_gray: color(palette.light_gray),
+            light_color: color(palette.light_color),
+            dark_color: color(palette.dark_color),
+        }
+    }
+}
+
+/// How the icon's grid is placed within non-square icons. See `Alignment` for more information.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RdenticonAlignment {
+    /// The grid is kept square and centered along the longer axis.
+    Center,
+    /// The grid is stretched to fill the padded area.
+    Fit,
+    /// The grid is kept square and repeated along the longer axis.
+    Tile,
+}
+
+impl FromRaw for RdenticonAlignment {
+    const VARIANTS: &'static [Self] = &[Self::Center, Self::Fit, Self::Tile];
+}
+
+impl From<RdenticonAlignment> for Alignment {
+    fn from(alignment: RdenticonAlignment) -> Self {
+        match alignment {
+            RdenticonAlignment::Center => Self::Center,
+            RdenticonAlignment::Fit => Self::Fit,
+            RdenticonAlignment::Tile => Self::Tile,
+        }
+    }
+}
+
+/// How shapes are anti-aliased in raster output. See `Antialiasing` for more information.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RdenticonAntialiasing {
+    /// Shapes are drawn without anti-aliasing.
+    None,
+    /// Pixels are blended by the exact fraction of their area that shapes cover.
+    Coverage,
+    /// Shapes are supersampled and downscaled with a box filter.
+    SupersampleBox,
+    /// Shapes are supersampled and downscaled with a Lanczos filter with 3 lobes.
+    SupersampleLanczos3,
+}
+
+impl FromRaw for RdenticonAntialiasing {
+    const VARIANTS: &'static [Self] = &[
+        Self::None,
+        Self::Coverage,
+        Self::SupersampleBox,
+        Self::SupersampleLanczos3,
+    ];
+}
+
+/// Error codes returned by the C bindings.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RdenticonError {
+    /// The call succeeded.
+    Ok = 0,
+    /// The hues are not within the range `[0.0, 360.0)`.
+    InvalidHues,
+    /// The color lightness is not within the range `0.0..=1.0`, or its start is greater than
+    /// its end.
+    InvalidColorLightness,
+    /// The grayscale lightness is not within the range `0.0..=1.0`, or its start is greater than
+    /// its end.
+    InvalidGrayscaleLightness,
+    /// The color saturation is not within the range `[0.0, 1.0]`.
+    InvalidColorSaturation,
+    /// The grayscale saturation is not within the range `[0.0, 1.0]`.
+    InvalidGrayscaleSaturation,
+    /// The padding is not within the range `[0.0, 0.5]`.
+    InvalidPadding,
+    /// A color is not a valid hex color.
+    MalformedColor,
+    /// A jdenticon configuration is not valid JSON.
+    MalformedJson,
+    /// The size of the icon is zero.
+    ZeroSize,
+    /// The destination buffer is too small to hold the icon.
+    DestinationTooSmall,
+    /// A required pointer argument is null.
+    NullPointer,
+    /// A string argument is not valid UTF-8.
+    InvalidUtf8,
+    /// The grid size is not within the range `4..=10`, or is not `4` in jdenticon compatibility
+    /// mode.
+    InvalidGridSize,
+    /// The shape set has no outer shapes or no center shapes.
+    EmptyShapeSet,
+    /// The minimum contrast ratio is not within the range `[1.0, 21.0]`.
+    InvalidMinContrast,
+    /// The supersampling factor is not within the range `1..=16`, or the supersampled icon is too
+    /// large to be held in memory.
+    InvalidSupersampling,
+    /// An enum argument is not one of the values of its enum, or a boolean argument is neither `0`
+    /// nor `1`.
+    InvalidArgument,
+    /// The icon is too large to be held in memory.
+    TooLarge,
+}
+
+impl FromRaw for RdenticonError {
+    const VARIANTS: &'static [Self] = &[
+        Self::Ok,
+        Self::InvalidHues,
+        Self::InvalidColorLightness,
+        Self::InvalidGrayscaleLightness,
+        Self::InvalidColorSaturation,
+        Self::InvalidGrayscaleSaturation,
+        Self::InvalidPadding,
+        Self::MalformedColor,
+        Self::MalformedJson,
+        Self::ZeroSize,
+        Self::DestinationTooSmall,
+        Self::NullPointer,
+        Self::InvalidUtf8,
+        Self::InvalidGridSize,
+        Self::EmptyShapeSet,
+        Self::InvalidMinContrast,
+        Self::InvalidSupersampling,
+        Self::InvalidArgument,
+        Self::TooLarge,
+    ];
+}
+
+impl From<ConfigBuilderError> for RdenticonError {
+    fn from(err: ConfigBuilderError) -> Self {
+        match err {
+            ConfigBuilderError::InvalidHues => Self::InvalidHues,
+            ConfigBuilderError::InvalidColorLightness => Self::InvalidColorLightness,
+            ConfigBuilderError::InvalidGrayscaleLightness => Self::InvalidGrayscaleLightness,
+            ConfigBuilderError::InvalidColorSaturation => Self::InvalidColorSaturation,
+            ConfigBuilderError::InvalidGrayscaleSaturation => Self::InvalidGrayscaleSaturation,
+            ConfigBuilderError::InvalidPadding => Self::InvalidPadding,
+            ConfigBuilderError::InvalidGridSize => Self::InvalidGridSize,
+            ConfigBuilderError::EmptyShapeSet => Self::EmptyShapeSet,
+            // Themes are not exposed through the C API
+            ConfigBuilderError::MismatchedThemeSize => Self::InvalidArgument,
+            ConfigBuilderError::InvalidMinContrast => Self::InvalidMinContrast,
+            ConfigBuilderError::InvalidSupersampling => Self::InvalidSupersampling,
+            ConfigBuilderError::MalformedColor(_) => Self::MalformedColor,
+            ConfigBuilderError::MalformedJson(_) => Self::MalformedJson,
+        }
+    }
+}
+
+impl From<RenderError> for RdenticonError {
+    fn from(err: RenderError) -> Self {
+        match err {
+            RenderError::InvalidConfig(err) => err.into(),
+            RenderError::ZeroSize => Self::ZeroSize,
+            RenderError::DestinationTooSmall => Self::DestinationTooSmall,
+        }
+    }
+}
+
+/// Returns a static, NUL-terminated description of the given `RdenticonError` code.
+#[no_mangle]
+pub extern "C" fn rdenticon_error_message(error: u32) -> *const c_char {
+    let Some(error) = RdenticonError::from_raw(error) else {
+        return c"unknown error code".as_ptr();
+    };
+    let message: &'static CStr = match error {
+        RdenticonError::Ok => c"ok",
+        RdenticonError::InvalidHues => c"hues must be within the range [0.0, 360.0)",
+        RdenticonError::InvalidColorLightness => {
+            c"color lightness must be within the range 0.0..=1.0"
+        }
+        RdenticonError::InvalidGrayscaleLightness => {
+            c"grayscale lightness must be within the range 0.0..=1.0"
+        }
+        RdenticonError::InvalidColorSaturation => {
+            c"color saturation must be within the range [0.0, 1.0]"
+        }
+        RdenticonError::InvalidGrayscaleSaturation => {
+            c"grayscale saturation must be within the range [0.0, 1.0]"
+        }
+        RdenticonError::InvalidPadding => c"padding must be within the range [0.0, 0.5]",
+        RdenticonError::MalformedColor => c"malformed color, expected a hex color like #rrggbbaa",
+        RdenticonError::MalformedJson => c"malformed jdenticon config",
+        RdenticonError::ZeroSize => c"size must be greater than zero",
+        RdenticonError::DestinationTooSmall => c"destination is too small to hold the icon",
+        RdenticonError::NullPointer => c"a required pointer argument is null",
+        RdenticonError::InvalidUtf8 => c"a string argument is not valid UTF-8",
+        RdenticonError::InvalidGridSize => {
+            c"grid size must be within the range 4..=10, and 4 in jdenticon compatibility mode"
+        }
+        RdenticonError::EmptyShapeSet => {
+            c"shape sets must have at least one outer and one center shape"
+        }
+        RdenticonError::InvalidMinContrast => {
+            c"minimum contrast must be within the range [1.0, 21.0]"
+        }
+        RdenticonError::InvalidSupersampling => {
+            c"supersampling factor must be within the range 1..=16 and small enough for the size"
+        }
+        RdenticonError::InvalidArgument => c"an enum or boolean argument is not one of its values",
+        RdenticonError::TooLarge => c"icon is too large to be held in memory",
+    };
+    message.as_ptr()
+}
+
+/// Creates a new configuration with default values. Free it with [`rdenticon_config_free`].
+#[no_mangle]
+pub extern "C" fn rdenticon_config_new() -> *mut RdenticonConfig {
+    Box::into_raw(Box::new(RdenticonConfig(Config::default())))
+}
+
+/// Frees a configuration created with [`rdenticon_config_new`]. Does nothing if `config` is null.
+///
+/// # Safety
+/// `config` must be null or a pointer returned by [`rdenticon_config_new`] that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_free(config: *mut RdenticonConfig) {
+    if !config.is_null() {
+        drop(Box::from_raw(config));
+    }
+}
+
+/// Runs `f` on the configuration behind `config`, or returns [`RdenticonError::NullPointer`].
+///
+/// # Safety
+/// `config` must be null or a valid pointer returned by [`rdenticon_config_new`].
+unsafe fn with_config(config: *mut RdenticonConfig, f: impl FnOnce(&mut Config)) -> RdenticonError {
+    match config.as_mut() {
+        Some(RdenticonConfig(config)) => {
+            f(config);
+            RdenticonError::Ok
+        }
+        None => RdenticonError::NullPointer,
+    }
+}
+
+/// Sets the hues to be used in the identicon, in degrees. `hues` may be null if `len` is zero,
+/// in which case all hues are allowed.
+///
+/// # Safety
+/// `config` must be a valid configuration, and `hues` must point to `len` doubles.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_hues(
+    config: *mut RdenticonConfig,
+    hues: *const f64,
+    len: usize,
+) -> RdenticonError {
+    if hues.is_null() && len != 0 {
+        return RdenticonError::NullPointer;
+    }
+
+    let hues = if len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(hues, len).to_vec()
+    };
+    with_config(config, |config| config.hues = hues)
+}
+
+/// Sets the color space that colors are derived from a hue in, as one of the
+/// `RdenticonColorSpace` values.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_color_space(
+    config: *mut RdenticonConfig,
+    color_space: u32,
+) -> RdenticonError {
+    let Some(color_space) = RdenticonColorSpace::from_raw(color_space) else {
+        return RdenticonError::InvalidArgument;
+    };
+    with_config(config, |config| config.color_space = color_space.into())
+}
+
+/// Sets fixed palettes to pick the colors of the identicon from. `palettes` may be null if `len`
+/// is zero, in which case colors are derived from a hue.
+///
+/// # Safety
+/// `config` must be a valid configuration, and `palettes` must point to `len` palettes.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_palettes(
+    config: *mut RdenticonConfig,
+    palettes: *const RdenticonPalette,
+    len: usize,
+) -> RdenticonError {
+    if palettes.is_null() && len != 0 {
+        return RdenticonError::NullPointer;
+    }
+
+    let palettes = if len == 0 {
+        Vec::new()
+    } else {
+        slice::from_raw_parts(palettes, len)
+            .iter()
+            .map(|&palette| palette.into())
+            .collect()
+    };
+    with_config(config, |config| config.palettes = palettes)
+}
+
+/// Sets the lightness range of colored shapes in the identicon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_color_lightness(
+    config: *mut RdenticonConfig,
+    min: f64,
+    max: f64,
+) -> RdenticonError {
+    with_config(config, |config| config.color_lightness = min..=max)
+}
+
+/// Sets the lightness range of grayscale shapes in the identicon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_grayscale_lightness(
+    config: *mut RdenticonConfig,
+    min: f64,
+    max: f64,
+) -> RdenticonError {
+    with_config(config, |config| config.grayscale_lightness = min..=max)
+}
+
+/// Sets the saturation of colored shapes in the identicon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_color_saturation(
+    config: *mut RdenticonConfig,
+    saturation: f64,
+) -> RdenticonError {
+    with_config(config, |config| config.color_saturation = saturation)
+}
+
+/// Sets the saturation of grayscale shapes in the identicon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_grayscale_saturation(
+    config: *mut RdenticonConfig,
+    saturation: f64,
+) -> RdenticonError {
+    with_config(config, |config| config.grayscale_saturation = saturation)
+}
+
+/// Sets the background color to be rendered behind the identicon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_background_color(
+    config: *mut RdenticonConfig,
+    r: u8,
+    g: u8,
+    b: u8,
+    a: u8,
+) -> RdenticonError {
+    with_config(config, |config| {
+        config.background_color = Rgba { r, g, b, a }
+    })
+}
+
+/// Sets the minimum WCAG contrast ratio of the colors of the identicon against the background
+/// color. Pass `0.0` to disable the check, which is the default.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_min_contrast(
+    config: *mut RdenticonConfig,
+    ratio: f64,
+) -> RdenticonError {
+    with_config(config, |config| {
+        config.min_contrast = (ratio != 0.0).then_some(ratio);
+    })
+}
+
+/// Sets whether to mirror the lightness of every color role, as `0` for false or `1` for true.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_mirror_lightness(
+    config: *mut RdenticonConfig,
+    mirror: u32,
+) -> RdenticonError {
+    let mirror = match mirror {
+        0 => false,
+        1 => true,
+        _ => return RdenticonError::InvalidArgument,
+    };
+    with_config(config, |config| config.mirror_lightness = mirror)
+}
+
+/// Sets the padding surrounding the icon relative to the size of the icon.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_padding(
+    config: *mut RdenticonConfig,
+    padding: f64,
+) -> RdenticonError {
+    with_config(config, |config| config.padding = padding)
+}
+
+/// Sets both the width and height of the icon in pixels.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_size(
+    config: *mut RdenticonConfig,
+    size: u32,
+) -> RdenticonError {
+    with_config(config, |config| {
+        config.width = size;
+        config.height = size;
+    })
+}
+
+/// Sets the width of the icon in pixels.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_width(
+    config: *mut RdenticonConfig,
+    width: u32,
+) -> RdenticonError {
+    with_config(config, |config| config.width = width)
+}
+
+/// Sets the height of the icon in pixels.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_height(
+    config: *mut RdenticonConfig,
+    height: u32,
+) -> RdenticonError {
+    with_config(config, |config| config.height = height)
+}
+
+/// Sets how the icon's grid is placed within non-square icons, as one of the
+/// `RdenticonAlignment` values.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_alignment(
+    config: *mut RdenticonConfig,
+    alignment: u32,
+) -> RdenticonError {
+    let Some(alignment) = RdenticonAlignment::from_raw(alignment) else {
+        return RdenticonError::InvalidArgument;
+    };
+    with_config(config, |config| config.alignment = alignment.into())
+}
+
+/// Sets how shapes are anti-aliased in raster output, as one of the `RdenticonAntialiasing`
+/// values. `factor` is how many times larger than the
+/// icon shapes are supersampled along each axis, within the range `1..=16`, and is ignored unless
+/// `antialiasing` is one of the supersampling modes.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_antialiasing(
+    config: *mut RdenticonConfig,
+    antialiasing: u32,
+    factor: u32,
+) -> RdenticonError {
+    let Some(antialiasing) = RdenticonAntialiasing::from_raw(antialiasing) else {
+        return RdenticonError::InvalidArgument;
+    };
+    let supersample = |filter| Antialiasing::Supersample { factor, filter };
+    let antialiasing = match antialiasing {
+        RdenticonAntialiasing::None => Antialiasing::None,
+        RdenticonAntialiasing::Coverage => Antialiasing::Coverage,
+        RdenticonAntialiasing::SupersampleBox => supersample(DownscaleFilter::Box),
+        RdenticonAntialiasing::SupersampleLanczos3 => supersample(DownscaleFilter::Lanczos3),
+    };
+    with_config(config, |config| config.antialiasing = antialiasing)
+}
+
+/// Sets the amount of cells along each side of the icon's grid, within the range `4..=10`.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_grid_size(
+    config: *mut RdenticonConfig,
+    grid_size: u32,
+) -> RdenticonError {
+    with_config(config, |config| config.grid_size = grid_size)
+}
+
+/// Sets whether to strictly follow jdenticon's geometry and color conversion, as `0` for false
+/// or `1` for true.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_set_jdenticon_compat(
+    config: *mut RdenticonConfig,
+    jdenticon_compat: u32,
+) -> RdenticonError {
+    let jdenticon_compat = match jdenticon_compat {
+        0 => false,
+        1 => true,
+        _ => return RdenticonError::InvalidArgument,
+    };
+    with_config(config, |config| config.jdenticon_compat = jdenticon_compat)
+}
+
+/// Validates the configuration, returning the same errors as `ConfigBuilder::build`.
+///
+/// # Safety
+/// `config` must be a valid configuration.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_config_build(config: *const RdenticonConfig) -> RdenticonError {
+    match config.as_ref() {
+        Some(RdenticonConfig(config)) => match config.validate() {
+            Ok(()) => RdenticonError::Ok,
+            Err(err) => err.into(),
+        },
+        None => RdenticonError::NullPointer,
+    }
+}
+
+/// Hashes a NUL-terminated message with SHA-1, like `generate_identicon`.
+///
+/// # Safety
+/// `message` must be null or a valid NUL-terminated string.
+unsafe fn hash_message(message: *const c_char) -> Result<[u8; 20], RdenticonError> {
+    if message.is_null() {
+        return Err(RdenticonError::NullPointer);
+    }
+
+    let message = CStr::from_ptr(message)
+        .to_str()
+        .map_err(|_| RdenticonError::InvalidUtf8)?;
+    Ok(reduce_digest(Sha1::digest(message.as_bytes()).as_ref()))
+}
+
+/// Reads a 20-byte hash.
+///
+/// # Safety
+/// `hash` must be null or point to 20 bytes.
+unsafe fn read_hash(hash: *const u8) -> Result<[u8; 20], RdenticonError> {
+    if hash.is_null() {
+        return Err(RdenticonError::NullPointer);
+    }
+    Ok(ptr::read(hash.cast::<[u8; 20]>()))
+}
+
+/// Renders as RGBA pixels and writes the buffer to `out` and its length to `out_len`.
+///
+/// # Safety
+/// All pointers must be null or valid.
+unsafe fn render_rgba(
+    hash: Result<[u8; 20], RdenticonError>,
+    config: *const RdenticonConfig,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> RdenticonError {
+    let (Some(RdenticonConfig(config)), false, false) =
+        (config.as_ref(), out.is_null(), out_len.is_null())
+    else {
+        return RdenticonError::NullPointer;
+    };
+    let hash = match hash {
+        Ok(hash) => hash,
+        Err(err) => return err,
+    };
+
+    if let Err(err) = config.validate() {
+        return err.into();
+    }
+    if config.width == 0 || config.height == 0 {
+        return RdenticonError::ZeroSize;
+    }
+
+    // Panicking or aborting on allocation failure would take the host process down with it
+    let (width, height) = (config.width as usize, config.height as usize);
+    let Some(len) = width.checked_mul(height).and_then(|len| len.checked_mul(4)) else {
+        return RdenticonError::TooLarge;
+    };
+    let mut buffer = Vec::new();
+    if buffer.try_reserve_exact(len).is_err() {
+        return RdenticonError::TooLarge;
+    }
+    buffer.resize(len, 0);
+    let mut buffer = buffer.into_boxed_slice();
+    if let Err(err) = render_identicon_into(hash, config, &mut buffer, width * 4) {
+        return err.into();
+    }
+
+    *out_len = buffer.len();
+    *out = Box::into_raw(buffer).cast::<u8>();
+    RdenticonError::Ok
+}
+
+/// Renders as an SVG document and writes the NUL-terminated string to `out`.
+///
+/// # Safety
+/// All pointers must be null or valid.
+unsafe fn render_svg(
+    hash: Result<[u8; 20], RdenticonError>,
+    config: *const RdenticonConfig,
+    out: *mut *mut c_char,
+) -> RdenticonError {
+    let (Some(RdenticonConfig(config)), false) = (config.as_ref(), out.is_null()) else {
+        return RdenticonError::NullPointer;
+    };
+    let hash = match hash {
+        Ok(hash) => hash,
+        Err(err) => return err,
+    };
+    if let Err(err) = config.validate() {
+        return err.into();
+    }
+
+    let svg = render_identicon_svg(hash, config);
+    *out = CString::new(svg)
+        .expect("SVG documents contain no NUL bytes")
+        .into_raw();
+    RdenticonError::Ok
+}
+
+/// Renders an identicon for a 20-byte hash as `width * height` RGBA pixels, row by row. On
+/// success, the buffer is written to `out` and its length in bytes to `out_len`. Free the buffer
+/// with [`rdenticon_rgba_free`]. The pixels are the same as those of `render_identicon`, and of
+/// the other bindings, for the same hash and configuration.
+///
+/// Returns `RdenticonError::TooLarge` if the buffer cannot be allocated.
+///
+/// # Safety
+/// `hash` must point to 20 bytes, `config` must be a valid configuration, and `out` and
+/// `out_len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_render_rgba(
+    hash: *const u8,
+    config: *const RdenticonConfig,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> RdenticonError {
+    render_rgba(read_hash(hash), config, out, out_len)
+}
+
+/// Generates an identicon for a NUL-terminated UTF-8 message, hashed with SHA-1, as RGBA pixels.
+/// See [`rdenticon_render_rgba`] for more information.
+///
+/// # Safety
+/// `message` must be a valid NUL-terminated string, `config` must be a valid configuration, and
+/// `out` and `out_len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_generate_rgba(
+    message: *const c_char,
+    config: *const RdenticonConfig,
+    out: *mut *mut u8,
+    out_len: *mut usize,
+) -> RdenticonError {
+    render_rgba(hash_message(message), config, out, out_len)
+}
+
+/// Renders an identicon for a 20-byte hash as an SVG document. On success, a NUL-terminated
+/// string is written to `out`. Free it with [`rdenticon_string_free`].
+///
+/// # Safety
+/// `hash` must point to 20 bytes, `config` must be a valid configuration, and `out` must be
+/// valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_render_svg(
+    hash: *const u8,
+    config: *const RdenticonConfig,
+    out: *mut *mut c_char,
+) -> RdenticonError {
+    render_svg(read_hash(hash), config, out)
+}
+
+/// Generates an identicon for a NUL-terminated UTF-8 message, hashed with SHA-1, as an SVG
+/// document. See [`rdenticon_render_svg`] for more information.
+///
+/// # Safety
+/// `message` must be a valid NUL-terminated string, `config` must be a valid configuration, and
+/// `out` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_generate_svg(
+    message: *const c_char,
+    config: *const RdenticonConfig,
+    out: *mut *mut c_char,
+) -> RdenticonError {
+    render_svg(hash_message(message), config, out)
+}
+
+/// Frees a buffer returned by [`rdenticon_render_rgba`] or [`rdenticon_generate_rgba`]. Does
+/// nothing if `buffer` is null.
+///
+/// # Safety
+/// `buffer` must be null or a buffer returned by rdenticon that was not freed, and `len` must be
+/// the length that was returned with it.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_rgba_free(buffer: *mut u8, len: usize) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer, len)));
+    }
+}
+
+/// Frees a string returned by [`rdenticon_render_svg`] or [`rdenticon_generate_svg`]. Does
+/// nothing if `string` is null.
+///
+/// # Safety
+/// `string` must be null or a string returned by rdenticon that was not freed.
+#[no_mangle]
+pub unsafe extern "C" fn rdenticon_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
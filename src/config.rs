@@ -1,3 +1,4 @@
+use crate::{Palette, ShapeSet};
 pub use ril::{Rgb, Rgba};
 use std::{
     fmt,
@@ -8,6 +9,12 @@ use std::{
 ///
 /// For checked inputs and to otherwise avoid panics at runtime, it is advised you use
 /// [`Config::builder`] to construct a [`Config`].
+///
+/// With the `serde` feature enabled, [`Config`] implements `Serialize` and `Deserialize`. The
+/// background color is represented as a `#rrggbbaa` hex string and lightness ranges as
+/// `[min, max]` arrays. Missing fields take their default values, and deserialized configurations
+/// are checked with [`Config::validate`]. A `size` field sets both the width and height, except
+/// for either of them that is set explicitly.
 #[derive(Clone, Debug)]
 pub struct Config {
     /// Limits the amount of hues in the identicon to only those specified in this `Vec`. All hues
@@ -25,13 +32,163 @@ pub struct Config {
     pub color_saturation: f64,
     /// Specifies the saturation range of grayscale shapes in the identicon, between 0 and 1.
     pub grayscale_saturation: f64,
+    /// The color space that colors are derived from a hue in. This determines how lightness and
+    /// saturation are interpreted. Defaults to [`ColorSpace::Hsl`].
+    ///
+    /// This is ignored when [`Config::jdenticon_compat`] is set, which always uses jdenticon's HSL
+    /// conversion.
+    pub color_space: ColorSpace,
+    /// Fixed palettes to pick the colors of the identicon from, instead of deriving them from a
+    /// hue. The hash picks one palette from this `Vec`, and all other color options except
+    /// [`Config::background_color`] and [`Config::mirror_lightness`] are ignored.
+    ///
+    /// If an empty `Vec` is provided, colors are derived from a hue. Defaults to an empty `Vec`.
+    pub palettes: Vec<Palette>,
     /// The background color to be rendered behind the identicon. Defaults to [`Rgba::white`].
     pub background_color: Rgba,
+    /// The minimum WCAG contrast ratio of the colors of the identicon against
+    /// [`Config::background_color`], within the range `[1.0, 21.0]`. For example, `3.0` is the
+    /// minimum ratio WCAG recommends for graphics. Defaults to `None`, which disables the check.
+    ///
+    /// Colors below the ratio are lightened or darkened until they reach it, and the roles that
+    /// were adjusted are reported by [`IdenticonSpec::adjusted_roles`]. The alpha channels of
+    /// colors are ignored when measuring contrast.
+    ///
+    /// [`IdenticonSpec::adjusted_roles`]: crate::IdenticonSpec::adjusted_roles
+    pub min_contrast: Option<f64>,
+    /// Whether to mirror the lightness of every color role, so that each role takes `1 - l` of
+    /// the lightness `l` it would otherwise have. For example, the dark gray role becomes as light
+    /// as the light gray role would be, and vice versa. This also mirrors the HSL lightness of the
+    /// colors of [`Config::palettes`], but not [`Config::background_color`]. Defaults to `false`.
+    ///
+    /// This is how [`ThemedConfig::from_light`] derives a dark variant: every role keeps the
+    /// contrast it has against a background whose lightness is mirrored as well.
+    ///
+    /// [`ThemedConfig::from_light`]: crate::ThemedConfig::from_light
+    pub mirror_lightness: bool,
     /// The padding surrounding the icon relative to the size of the icon. This should be within
     /// the range `[0.0, 0.5]`. Defaults to `0.08`.
+    ///
+    /// Padding is computed per axis, so horizontal padding is relative to [`Config::width`] and
+    /// vertical padding is relative to [`Config::height`].
     pub padding: f64,
-    /// The size of the icon in pixels. Defaults to `256`.
-    pub size: u32,
+    /// The width of the icon in pixels. Defaults to `256`.
+    ///
+    /// Together with [`Config::height`], this replaces the `size` field of earlier versions. Use
+    /// [`ConfigBuilder::size`] to set both at once.
+    pub width: u32,
+    /// The height of the icon in pixels. Defaults to `256`.
+    pub height: u32,
+    /// How the icon's grid is placed within the padded area when [`Config::width`] and
+    /// [`Config::height`] differ. Defaults to [`Alignment::Center`].
+    pub alignment: Alignment,
+    /// How shapes are anti-aliased in raster output. Defaults to [`Antialiasing::None`].
+    ///
+    /// SVG output is unaffected, since SVG viewers anti-alias shapes on their own. Anti-aliasing
+    /// mostly matters for small icons, such as favicons, whose edges otherwise look jagged.
+    pub antialiasing: Antialiasing,
+    /// The amount of cells along each side of the icon's grid, within the range `4..=10`.
+    /// Defaults to `4`.
+    ///
+    /// The grid is made of rings of corner and side cells around a center of 2 by 2 cells, or a
+    /// single cell for odd sizes. Denser grids are better suited to large icons.
+    pub grid_size: u32,
+    /// The shapes that the cells of the grid are drawn with. Defaults to the built-in shapes,
+    /// which are the same as jdenticon's. See [`ShapeSet`] for more information.
+    pub shapes: ShapeSet,
+    /// Whether to strictly follow jdenticon's geometry and color conversion, so that SVG output
+    /// matches jdenticon's `toSvg` for the same hash and configuration. Defaults to `false`.
+    ///
+    /// Note that jdenticon does not render a background unless one is configured, which
+    /// corresponds to a [`Rgba::transparent`] background here.
+    pub jdenticon_compat: bool,
+}
+
+/// How the icon's grid is placed within the padded area of an icon whose width and height differ.
+/// For square icons, all alignments render the same icon.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Alignment {
+    /// The grid is kept square, as large as the shorter axis allows, and centered along the
+    /// longer axis.
+    #[default]
+    Center,
+    /// The grid is stretched to fill the padded area, so that cells and shapes are no longer
+    /// square.
+    Fit,
+    /// The grid is kept square like [`Alignment::Center`], and repeated along the longer axis as
+    /// many times as it fits. The tiles are centered as a whole.
+    Tile,
+}
+
+/// How shapes are anti-aliased in raster output. See [`Config::antialiasing`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Antialiasing {
+    /// Shapes are drawn without anti-aliasing, and each pixel takes the color of whichever shape
+    /// covers it.
+    #[default]
+    None,
+    /// Shapes are drawn at `factor` times the width and height of the icon, then downscaled with
+    /// `filter`. The factor should be within the range `1..=16`; `4` is usually enough.
+    Supersample {
+        /// How many times larger than the icon the shapes are drawn along each axis.
+        factor: u32,
+        /// The filter that the supersampled icon is downscaled with.
+        filter: DownscaleFilter,
+    },
+    /// Each pixel is blended with the colors of the shapes by the exact fraction of its area that
+    /// they cover. This is as smooth as supersampling with a large factor, without drawing the
+    /// icon at a larger size.
+    Coverage,
+}
+
+/// A filter that supersampled icons are downscaled with. See [`Antialiasing::Supersample`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum DownscaleFilter {
+    /// Averages the samples within each pixel. Edges are smooth, but slightly soft.
+    #[default]
+    Box,
+    /// A Lanczos filter with 3 lobes, which keeps edges sharper than [`DownscaleFilter::Box`] at
+    /// the cost of slight ringing next to high-contrast edges.
+    Lanczos3,
+}
+
+/// A color space that the colors of an identicon are derived from a hue in.
+///
+/// In every color space, lightness and saturation are within the range `[0.0, 1.0]`, so the same
+/// [`Config`] ranges can be used with any of them. In the LCh color spaces, colors that do not fit
+/// within sRGB have their chroma reduced, which keeps their lightness and hue intact.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ColorSpace {
+    /// HSL, with lightness corrected for each hue the same way jdenticon does. Perceived
+    /// brightness still varies noticeably between hues, for example between yellow and blue.
+    #[default]
+    Hsl,
+    /// OKLCH, where lightness is OKLab's perceptual lightness and saturation is chroma relative to
+    /// `0.4`. Colors of the same lightness look equally bright regardless of their hue.
+    Oklch,
+    /// CIELCh with a D65 white point, where lightness is CIELAB's `L*` divided by `100` and
+    /// saturation is chroma relative to `150`.
+    Cielch,
 }
 
 impl Default for Config {
@@ -42,14 +199,33 @@ impl Default for Config {
             grayscale_lightness: 0.3..=0.9,
             color_saturation: 0.5,
             grayscale_saturation: 0.0,
+            color_space: ColorSpace::Hsl,
+            palettes: Vec::new(),
             background_color: Rgba::white(),
+            min_contrast: None,
+            mirror_lightness: false,
             padding: 0.08,
-            size: 256,
+            width: 256,
+            height: 256,
+            alignment: Alignment::Center,
+            antialiasing: Antialiasing::None,
+            grid_size: 4,
+            shapes: ShapeSet::default(),
+            jdenticon_compat: false,
         }
     }
 }
 
 impl Config {
+    /// The size of the icon in pixels, which is its [`Config::width`]. This is kept for
+    /// compatibility with the `size` field of earlier versions, from before icons could be
+    /// non-square.
+    #[deprecated(note = "use `width` and `height` instead, since icons may be non-square")]
+    #[must_use]
+    pub const fn size(&self) -> u32 {
+        self.width
+    }
+
     /// Creates a new [`ConfigBuilder`] to construct a [`Config`].
     #[must_use = "ConfigBuilder does nothing on its own"]
     pub fn builder() -> ConfigBuilder {
@@ -57,6 +233,121 @@ impl Config {
             config: Config::default(),
         }
     }
+
+    /// Validates this configuration. This is done automatically by [`ConfigBuilder::build`], but
+    /// since the fields of [`Config`] are public, a [`Config`] constructed or modified by hand may
+    /// still be invalid.
+    ///
+    /// # Errors
+    /// * If hues are not within the range `[0.0, 360.0)`.
+    /// * If color lightness is not within the range `0.0..=1.0`, or its start is greater than its
+    ///   end.
+    /// * If grayscale lightness is not within the range `0.0..=1.0`, or its start is greater than
+    ///   its end.
+    /// * If color saturation is not within the range `[0.0, 1.0]`.
+    /// * If grayscale saturation is not within the range `[0.0, 1.0]`.
+    /// * If padding is not within the range `[0.0, 0.5]`.
+    /// * If the minimum contrast ratio is not within the range `[1.0, 21.0]`.
+    /// * If the supersampling factor is not within the range `1..=16`, or the supersampled icon
+    ///   is too large to be held in memory.
+    /// * If the grid size is not within the range `4..=10`, or is not `4` while
+    ///   [`Config::jdenticon_compat`] is set.
+    /// * If either list of shapes of [`Config::shapes`] is empty.
+    pub fn validate(&self) -> Result<(), ConfigBuilderError> {
+        if self.hues.iter().any(|hue| !(0.0..360.0).contains(hue)) {
+            return Err(ConfigBuilderError::InvalidHues);
+        }
+        // Written so that NaN bounds are rejected as well
+        let is_valid_lightness = |range: &RangeInclusive<f64>| {
+            (0.0..=1.0).contains(range.start())
+                && (0.0..=1.0).contains(range.end())
+                && range.start() <= range.end()
+        };
+        if !is_valid_lightness(&self.color_lightness) {
+            return Err(ConfigBuilderError::InvalidColorLightness);
+        }
+        if !is_valid_lightness(&self.grayscale_lightness) {
+            return Err(ConfigBuilderError::InvalidGrayscaleLightness);
+        }
+        if !(0.0..=1.0).contains(&self.color_saturation) {
+            return Err(ConfigBuilderError::InvalidColorSaturation);
+        }
+        if !(0.0..=1.0).contains(&self.grayscale_saturation) {
+            return Err(ConfigBuilderError::InvalidGrayscaleSaturation);
+        }
+        if !(0.0..=0.5).contains(&self.padding) {
+            return Err(ConfigBuilderError::InvalidPadding);
+        }
+        if self
+            .min_contrast
+            .is_some_and(|ratio| !(1.0..=21.0).contains(&ratio))
+        {
+            return Err(ConfigBuilderError::InvalidMinContrast);
+        }
+        if let Antialiasing::Supersample { factor, .. } = self.antialiasing {
+            // The icon is drawn at `factor` times its size before being downscaled
+            let len = self
+                .width
+                .checked_mul(factor)
+                .zip(self.height.checked_mul(factor));
+            let len = len.and_then(|(width, height)| {
+                (width as usize)
+                    .checked_mul(height as usize)?
+                    .checked_mul(4)
+            });
+            if !(1..=16).contains(&factor) || len.is_none() {
+                return Err(ConfigBuilderError::InvalidSupersampling);
+            }
+        }
+        if !(4..=10).contains(&self.grid_size) || self.jdenticon_compat && self.grid_size != 4 {
+            return Err(ConfigBuilderError::InvalidGridSize);
+        }
+        if self.shapes.outer.is_empty() || self.shapes.center.is_empty() {
+            return Err(ConfigBuilderError::EmptyShapeSet);
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` hex color. The leading `#` is optional.
+///
+/// Returns `None` if the color is malformed.
+#[must_use]
+pub fn parse_hex_color(s: &str) -> Option<Rgba> {
+    let s = s.strip_prefix('#').unwrap_or(s);
+    // `u8::from_str_radix` would also accept a sign before each channel
+    if !s.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    let channel = |i: usize, width: usize| {
+        let value = u8::from_str_radix(&s[i * width..(i + 1) * width], 16).ok()?;
+        Some(if width == 1 { value * 0x11 } else { value })
+    };
+    let (width, has_alpha) = match s.len() {
+        3 => (1, false),
+        4 => (1, true),
+        6 => (2, false),
+        8 => (2, true),
+        _ => return None,
+    };
+
+    Some(Rgba {
+        r: channel(0, width)?,
+        g: channel(1, width)?,
+        b: channel(2, width)?,
+        a: if has_alpha { channel(3, width)? } else { 255 },
+    })
+}
+
+/// Formats a color as a `#rrggbbaa` hex color.
+#[cfg(feature = "serde")]
+pub(crate) fn format_hex_color(color: Rgba) -> String {
+    format!(
+        "#{:02x}{:02x}{:02x}{:02x}",
+        color.r, color.g, color.b, color.a
+    )
 }
 
 /// A builder for [`Config`]s.
@@ -146,6 +437,22 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the color space that colors are derived from a hue in.
+    /// Defaults to [`ColorSpace::Hsl`].
+    #[must_use = "This method does not modify in place"]
+    pub const fn color_space(mut self, color_space: ColorSpace) -> Self {
+        self.config.color_space = color_space;
+        self
+    }
+
+    /// Sets fixed palettes to pick the colors of the identicon from, instead of deriving them from
+    /// a hue. See [`Config::palettes`] for more information.
+    #[must_use = "This method does not modify in place"]
+    pub fn palettes(mut self, palettes: impl AsRef<[Palette]>) -> Self {
+        self.config.palettes = palettes.as_ref().to_vec();
+        self
+    }
+
     /// Sets the background color to be rendered behind the identicon.
     /// Defaults to [`Rgba::white`].
     #[must_use = "This method does not modify in place"]
@@ -154,6 +461,23 @@ impl ConfigBuilder {
         self
     }
 
+    /// Sets the minimum WCAG contrast ratio of the colors of the identicon against the background
+    /// color. This should be within the range `[1.0, 21.0]`. See [`Config::min_contrast`] for more
+    /// information.
+    #[must_use = "This method does not modify in place"]
+    pub const fn min_contrast(mut self, ratio: f64) -> Self {
+        self.config.min_contrast = Some(ratio);
+        self
+    }
+
+    /// Sets whether to mirror the lightness of every color role. See [`Config::mirror_lightness`]
+    /// for more information.
+    #[must_use = "This method does not modify in place"]
+    pub const fn mirror_lightness(mut self, mirror: bool) -> Self {
+        self.config.mirror_lightness = mirror;
+        self
+    }
+
     /// Sets the padding surrounding the icon relative to the size of the icon.
     /// This should be within the range `[0.0, 0.5]`. Defaults to `0.08`.
     #[must_use = "This method does not modify in place"]
@@ -162,48 +486,72 @@ impl ConfigBuilder {
         self
     }
 
-    /// Sets the size of the icon in pixels. Defaults to `256`.
+    /// Sets both the width and height of the icon in pixels. Defaults to `256`.
     #[must_use = "This method does not modify in place"]
     pub const fn size(mut self, size: u32) -> Self {
-        self.config.size = size;
+        self.config.width = size;
+        self.config.height = size;
+        self
+    }
+
+    /// Sets the width of the icon in pixels. Defaults to `256`.
+    #[must_use = "This method does not modify in place"]
+    pub const fn width(mut self, width: u32) -> Self {
+        self.config.width = width;
+        self
+    }
+
+    /// Sets the height of the icon in pixels. Defaults to `256`.
+    #[must_use = "This method does not modify in place"]
+    pub const fn height(mut self, height: u32) -> Self {
+        self.config.height = height;
+        self
+    }
+
+    /// Sets how the icon's grid is placed within non-square icons.
+    /// Defaults to [`Alignment::Center`].
+    #[must_use = "This method does not modify in place"]
+    pub const fn alignment(mut self, alignment: Alignment) -> Self {
+        self.config.alignment = alignment;
+        self
+    }
+
+    /// Sets how shapes are anti-aliased in raster output. Defaults to [`Antialiasing::None`].
+    #[must_use = "This method does not modify in place"]
+    pub const fn antialiasing(mut self, antialiasing: Antialiasing) -> Self {
+        self.config.antialiasing = antialiasing;
+        self
+    }
+
+    /// Sets the amount of cells along each side of the icon's grid. This should be within the
+    /// range `4..=10`. Defaults to `4`.
+    #[must_use = "This method does not modify in place"]
+    pub const fn grid_size(mut self, grid_size: u32) -> Self {
+        self.config.grid_size = grid_size;
+        self
+    }
+
+    /// Sets the shapes that the cells of the grid are drawn with. Defaults to the built-in shapes.
+    #[must_use = "This method does not modify in place"]
+    pub fn shapes(mut self, shapes: ShapeSet) -> Self {
+        self.config.shapes = shapes;
+        self
+    }
+
+    /// Sets whether to strictly follow jdenticon's geometry and color conversion, so that SVG
+    /// output matches jdenticon's `toSvg`. Defaults to `false`.
+    #[must_use = "This method does not modify in place"]
+    pub const fn jdenticon_compat(mut self, jdenticon_compat: bool) -> Self {
+        self.config.jdenticon_compat = jdenticon_compat;
         self
     }
 
     /// Builds the [`Config`].
     ///
     /// # Errors
-    /// * If hues are not within the range `[0.0, 360.0)`.
-    /// * If color lightness is not within the range `0.0..=1.0`.
-    /// * If grayscale lightness is not within the range `0.0..=1.0`.
-    /// * If color saturation is not within the range `[0.0, 1.0]`.
-    /// * If grayscale saturation is not within the range `[0.0, 1.0]`.
-    /// * If padding is not within the range `[0.0, 0.5]`.
+    /// * If the configuration is invalid. See [`Config::validate`] for more information.
     pub fn build(self) -> Result<Config, ConfigBuilderError> {
-        if self
-            .config
-            .hues
-            .iter()
-            .any(|hue| !(0.0..360.0).contains(hue))
-        {
-            return Err(ConfigBuilderError::InvalidHues);
-        }
-        if self.config.color_lightness.start() < &0.0 || self.config.color_lightness.end() > &1.0 {
-            return Err(ConfigBuilderError::InvalidColorLightness);
-        }
-        if self.config.grayscale_lightness.start() < &0.0
-            || self.config.grayscale_lightness.end() > &1.0
-        {
-            return Err(ConfigBuilderError::InvalidGrayscaleLightness);
-        }
-        if !(0.0..=1.0).contains(&self.config.color_saturation) {
-            return Err(ConfigBuilderError::InvalidColorSaturation);
-        }
-        if !(0.0..=1.0).contains(&self.config.grayscale_saturation) {
-            return Err(ConfigBuilderError::InvalidGrayscaleSaturation);
-        }
-        if !(0.0..=0.5).contains(&self.config.padding) {
-            return Err(ConfigBuilderError::InvalidPadding);
-        }
+        self.config.validate()?;
 
         Ok(self.config)
     }
@@ -215,9 +563,11 @@ impl ConfigBuilder {
 pub enum ConfigBuilderError {
     /// The hues are not within the range `[0.0, 360.0)`.
     InvalidHues,
-    /// The color lightness is not within the range `0.0..=1.0`.
+    /// The color lightness is not within the range `0.0..=1.0`, or its start is greater than
+    /// its end.
     InvalidColorLightness,
-    /// The grayscale lightness is not within the range `0.0..=1.0`.
+    /// The grayscale lightness is not within the range `0.0..=1.0`, or its start is greater than
+    /// its end.
     InvalidGrayscaleLightness,
     /// The color saturation is not within the range `[0.0, 1.0]`.
     InvalidColorSaturation,
@@ -225,6 +575,23 @@ pub enum ConfigBuilderError {
     InvalidGrayscaleSaturation,
     /// The padding is not within the range `[0.0, 0.5]`.
     InvalidPadding,
+    /// The minimum contrast ratio is not within the range `[1.0, 21.0]`.
+    InvalidMinContrast,
+    /// The supersampling factor is not within the range `1..=16`, or the supersampled icon is too
+    /// large to be held in memory.
+    InvalidSupersampling,
+    /// The grid size is not within the range `4..=10`, or is not `4` in jdenticon compatibility
+    /// mode.
+    InvalidGridSize,
+    /// The shape set has no outer shapes or no center shapes.
+    EmptyShapeSet,
+    /// The light and dark variants of a [`ThemedConfig`](crate::ThemedConfig) do not have the
+    /// same width and height.
+    MismatchedThemeSize,
+    /// A color could not be parsed as a `#rgb`, `#rgba`, `#rrggbb` or `#rrggbbaa` hex color.
+    MalformedColor(String),
+    /// A jdenticon configuration could not be parsed as JSON.
+    MalformedJson(String),
 }
 
 impl fmt::Display for ConfigBuilderError {
@@ -240,6 +607,26 @@ impl fmt::Display for ConfigBuilderError {
                 "grayscale saturation must be within the range [0.0, 1.0]"
             }
             Self::InvalidPadding => "padding must be within the range [0.0, 0.5]",
+            Self::InvalidMinContrast => "minimum contrast must be within the range [1.0, 21.0]",
+            Self::InvalidSupersampling => {
+                "supersampling factor must be within the range 1..=16 and small enough for the size"
+            }
+            Self::InvalidGridSize => {
+                "grid size must be within the range 4..=10, and 4 in jdenticon compatibility mode"
+            }
+            Self::EmptyShapeSet => "shape sets must have at least one outer and one center shape",
+            Self::MismatchedThemeSize => {
+                "the light and dark variants of a theme must have the same width and height"
+            }
+            Self::MalformedColor(color) => {
+                return write!(
+                    f,
+                    "malformed color {color:?}, expected a hex color like #rrggbbaa"
+                );
+            }
+            Self::MalformedJson(message) => {
+                return write!(f, "malformed jdenticon config: {message}");
+            }
         };
         f.write_str(content)
     }
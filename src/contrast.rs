@@ -0,0 +1,86 @@
+use crate::Rgba;
+
+/// Returns the relative luminance of a color as defined by WCAG 2, ignoring its alpha channel.
+fn relative_luminance(color: Rgba) -> f64 {
+    let linear = |channel: u8| {
+        let value = f64::from(channel) / 255.0;
+        if value <= 0.040_45 {
+            value / 12.92
+        } else {
+            ((value + 0.055) / 1.055).powf(2.4)
+        }
+    };
+
+    0.0722f64.mul_add(
+        linear(color.b),
+        0.2126f64.mul_add(linear(color.r), 0.7152 * linear(color.g)),
+    )
+}
+
+/// Returns the contrast ratio between two colors as defined by WCAG 2, within the range
+/// `[1.0, 21.0]`. Alpha channels are ignored.
+///
+/// ```
+/// use rdenticon::{contrast_ratio, Rgba};
+///
+/// assert_eq!(contrast_ratio(Rgba::black(), Rgba::white()), 21.0);
+/// ```
+#[must_use]
+pub fn contrast_ratio(a: Rgba, b: Rgba) -> f64 {
+    let (a, b) = (relative_luminance(a), relative_luminance(b));
+    (a.max(b) + 0.05) / (a.min(b) + 0.05)
+}
+
+/// Mixes the color channels of a color with `target` by the fraction `t`, keeping its alpha.
+fn mix(color: Rgba, target: u8, t: f64) -> Rgba {
+    let channel = |value: u8| {
+        (f64::from(target) - f64::from(value))
+            .mul_add(t, f64::from(value))
+            .round() as u8
+    };
+    Rgba::new(
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        color.a,
+    )
+}
+
+/// Lightens or darkens a color as little as possible until its contrast ratio against the
+/// background is at least `min_ratio`.
+///
+/// Colors are moved away from the background first, that is, colors lighter than the background
+/// are lightened towards white and darker colors are darkened towards black. If the ratio cannot
+/// be reached that way, the color is moved the other way instead, and if it cannot be reached
+/// either way, whichever of black and white contrasts the most is returned.
+pub(crate) fn ensure_contrast(color: Rgba, background: Rgba, min_ratio: f64) -> Rgba {
+    if contrast_ratio(color, background) >= min_ratio {
+        return color;
+    }
+
+    let lighter = relative_luminance(color) >= relative_luminance(background);
+    let targets = if lighter { [255, 0] } else { [0, 255] };
+    for target in targets {
+        if contrast_ratio(mix(color, target, 1.0), background) < min_ratio {
+            continue;
+        }
+
+        // Binary search for the smallest amount of mixing that reaches the ratio
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..20 {
+            let mid = (low + high) / 2.0;
+            if contrast_ratio(mix(color, target, mid), background) >= min_ratio {
+                high = mid;
+            } else {
+                low = mid;
+            }
+        }
+        return mix(color, target, high);
+    }
+
+    targets
+        .map(|target| mix(color, target, 1.0))
+        .into_iter()
+        .max_by(|a, b| contrast_ratio(*a, background).total_cmp(&contrast_ratio(*b, background)))
+        .unwrap_or(color)
+}
@@ -0,0 +1,179 @@
+use crate::{into_nibbles, spec::pick_colors, ColorRole, Config, IdenticonSpec, Rgba, ShapeKind};
+
+/// A part of an identicon named by [`describe`], with the shape and color role of its cells.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct DescribedPart {
+    /// The shape that the cells of the part are drawn with.
+    pub shape: ShapeKind,
+    /// The color role of the part.
+    pub role: ColorRole,
+}
+
+/// The words that [`describe_with`] builds a description out of, which allows descriptions to be
+/// localized. See [`English`] for the words used by [`describe`].
+///
+/// Implementations that name the shapes of a custom [`ShapeSet`](crate::ShapeSet) should also
+/// implement this trait, since only the built-in shapes are named by [`English`].
+pub trait Words {
+    /// Names the color of the identicon given its hue in degrees, within the range
+    /// `[0.0, 360.0)`. This is the hue of its rendered color in RGB, so it matches the pixels in
+    /// every [`ColorSpace`](crate::ColorSpace). The hue is `None` for grayscale identicons.
+    fn color(&self, hue: Option<f64>) -> String;
+
+    /// Names a shape.
+    fn shape(&self, shape: ShapeKind) -> String;
+
+    /// Names a color role, given the name of the color of the identicon.
+    fn role(&self, role: ColorRole, color: &str) -> String;
+
+    /// Joins the name of the color of the identicon with the sides, corners and center of its
+    /// outermost ring into a description.
+    fn sentence(&self, color: &str, parts: [DescribedPart; 3]) -> String;
+}
+
+/// The English [`Words`] used by [`describe`], which describes identicons as, for example,
+/// `"teal identicon with dark gray triangle sides, light teal rhombus corners and a teal circle
+/// center"`.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct English;
+
+impl English {
+    const COLORS: [&'static str; 12] = [
+        "red", "orange", "yellow", "lime", "green", "mint", "teal", "azure", "blue", "purple",
+        "magenta", "pink",
+    ];
+
+    const OUTER_SHAPES: [&'static str; 4] = ["triangle", "flat triangle", "rhombus", "circle"];
+
+    const CENTER_SHAPES: [&'static str; 14] = [
+        "beveled square",
+        "narrow triangle",
+        "small square",
+        "inset square",
+        "small circle",
+        "punched square",
+        "notched square",
+        "small triangle",
+        "clipped square",
+        "square frame",
+        "ring",
+        "small triangle",
+        "hollow square",
+        "circle",
+    ];
+}
+
+impl Words for English {
+    fn color(&self, hue: Option<f64>) -> String {
+        hue.map_or("gray", |hue| {
+            // Each color word spans 30 degrees, centered on its hue
+            let index = ((hue + 15.0) / 30.0) as usize % Self::COLORS.len();
+            Self::COLORS[index]
+        })
+        .to_string()
+    }
+
+    fn shape(&self, shape: ShapeKind) -> String {
+        let name = match shape {
+            ShapeKind::Outer(index) => Self::OUTER_SHAPES.get(index as usize),
+            ShapeKind::Center(index) => Self::CENTER_SHAPES.get(index as usize),
+        };
+        name.copied().unwrap_or("shape").to_string()
+    }
+
+    fn role(&self, role: ColorRole, color: &str) -> String {
+        match role {
+            ColorRole::DarkGray => "dark gray".to_string(),
+            ColorRole::LightGray => "light gray".to_string(),
+            ColorRole::MidColor => color.to_string(),
+            // Grayscale identicons would otherwise name these the same as the gray roles
+            ColorRole::LightColor if color == "gray" => "silver".to_string(),
+            ColorRole::DarkColor if color == "gray" => "charcoal".to_string(),
+            ColorRole::LightColor => format!("light {color}"),
+            ColorRole::DarkColor => format!("dark {color}"),
+        }
+    }
+
+    fn sentence(&self, color: &str, [sides, corners, center]: [DescribedPart; 3]) -> String {
+        let part = |part: DescribedPart| {
+            format!("{} {}", self.role(part.role, color), self.shape(part.shape))
+        };
+        let center = part(center);
+        let article = if center.starts_with(['a', 'e', 'i', 'o', 'u']) {
+            "an"
+        } else {
+            "a"
+        };
+
+        format!(
+            "{color} identicon with {} sides, {} corners and {article} {center} center",
+            part(sides),
+            part(corners),
+        )
+    }
+}
+
+/// Returns the hue of a color in degrees, or `None` if it is a shade of gray.
+fn rgb_hue(color: Rgba) -> Option<f64> {
+    let [r, g, b] = [color.r, color.g, color.b].map(f64::from);
+    let (max, min) = (r.max(g).max(b), r.min(g).min(b));
+    let chroma = max - min;
+    // Colors that are nearly gray are not worth naming
+    if chroma < 8.0 {
+        return None;
+    }
+
+    let hue = if max == r {
+        (g - b) / chroma
+    } else if max == g {
+        (b - r) / chroma + 2.0
+    } else {
+        (r - g) / chroma + 4.0
+    };
+    Some((hue * 60.0).rem_euclid(360.0))
+}
+
+/// Describes the identicon for the given hash in English, for example as alt text for screen
+/// readers. The hash is strictly 20-bytes long, see [`render_identicon`] for more information.
+///
+/// The description names the color of the identicon, along with the shapes and color roles of
+/// the sides, corners and center of its outermost ring. See [`describe_with`] to describe
+/// identicons in other languages.
+///
+/// # Example
+/// ```
+/// use rdenticon::{describe, Config, IdenticonHasher, Sha1};
+///
+/// let description = describe(Sha1::digest(b"rdenticon"), &Config::default());
+/// assert!(description.contains(" identicon with "));
+/// ```
+///
+/// [`render_identicon`]: crate::render_identicon
+#[must_use]
+pub fn describe(hash: [u8; 20], config: &Config) -> String {
+    describe_with(hash, config, &English)
+}
+
+/// Describes the identicon for the given hash with the given [`Words`]. See [`describe`] for more
+/// information.
+#[must_use]
+pub fn describe_with(hash: [u8; 20], config: &Config, words: &impl Words) -> String {
+    let spec = IdenticonSpec::new(hash, config);
+    // The same hue looks different in each color space, so the color is named after the pixels
+    let (_, palette) = pick_colors(&into_nibbles(hash), config);
+    let hue = rgb_hue(palette.mid_color);
+
+    // The groups of the outermost ring come first, and the center comes last
+    let part = |index: usize, role: ColorRole| DescribedPart {
+        shape: spec.groups[index].shape,
+        role,
+    };
+    let [side_role, corner_role, center_role] = spec.roles;
+    let parts = [
+        part(0, side_role),
+        part(1, corner_role),
+        part(spec.groups.len() - 1, center_role),
+    ];
+
+    words.sentence(&words.color(hue), parts)
+}
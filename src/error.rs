@@ -0,0 +1,40 @@
+use crate::ConfigBuilderError;
+use std::fmt;
+
+/// An error that occurs when rendering an identicon.
+/// See [`try_render_identicon`](crate::try_render_identicon) for more information.
+#[derive(Clone, Debug)]
+pub enum RenderError {
+    /// The configuration is invalid. See [`Config::validate`](crate::Config::validate) for more
+    /// information.
+    InvalidConfig(ConfigBuilderError),
+    /// The size of the icon is zero.
+    ZeroSize,
+    /// The destination buffer or image is too small to hold the icon.
+    DestinationTooSmall,
+}
+
+impl From<ConfigBuilderError> for RenderError {
+    fn from(err: ConfigBuilderError) -> Self {
+        Self::InvalidConfig(err)
+    }
+}
+
+impl fmt::Display for RenderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidConfig(err) => write!(f, "invalid config: {err}"),
+            Self::ZeroSize => f.write_str("size must be greater than zero"),
+            Self::DestinationTooSmall => f.write_str("destination is too small to hold the icon"),
+        }
+    }
+}
+
+impl std::error::Error for RenderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidConfig(err) => Some(err),
+            Self::ZeroSize | Self::DestinationTooSmall => None,
+        }
+    }
+}
@@ -0,0 +1,110 @@
+/// A hash algorithm that can be used to generate identicons from messages.
+///
+/// See [`generate_identicon_with`](crate::generate_identicon_with) for more information.
+pub trait IdenticonHasher {
+    /// The digest produced by this hash algorithm. It must be at least 6 bytes long, see
+    /// [`reduce_digest`] for more information.
+    type Output: AsRef<[u8]>;
+
+    /// Hashes the given message.
+    fn digest(message: &[u8]) -> Self::Output;
+}
+
+/// The SHA-1 hash algorithm. This is the algorithm used by
+/// [`generate_identicon`](crate::generate_identicon) and by jdenticon.
+pub struct Sha1;
+
+impl IdenticonHasher for Sha1 {
+    type Output = [u8; 20];
+
+    fn digest(message: &[u8]) -> Self::Output {
+        sha1_smol::Sha1::from(message).digest().bytes()
+    }
+}
+
+/// The SHA-256 hash algorithm. Requires the `sha256` feature.
+#[cfg(feature = "sha256")]
+pub struct Sha256;
+
+#[cfg(feature = "sha256")]
+impl IdenticonHasher for Sha256 {
+    type Output = [u8; 32];
+
+    fn digest(message: &[u8]) -> Self::Output {
+        use sha2::Digest;
+
+        sha2::Sha256::digest(message).into()
+    }
+}
+
+/// The BLAKE3 hash algorithm. Requires the `blake3` feature.
+#[cfg(feature = "blake3")]
+pub struct Blake3;
+
+#[cfg(feature = "blake3")]
+impl IdenticonHasher for Blake3 {
+    type Output = [u8; 32];
+
+    fn digest(message: &[u8]) -> Self::Output {
+        blake3::hash(message).into()
+    }
+}
+
+/// The MD5 hash algorithm, as used by Gravatar for email hashes. Requires the `md5` feature.
+///
+/// MD5 is not cryptographically secure and should only be used for compatibility.
+#[cfg(feature = "md5")]
+pub struct Md5;
+
+#[cfg(feature = "md5")]
+impl IdenticonHasher for Md5 {
+    type Output = [u8; 16];
+
+    fn digest(message: &[u8]) -> Self::Output {
+        use md5::Digest;
+
+        md5::Md5::digest(message).into()
+    }
+}
+
+/// Reduces a digest of any length to the 20 bytes expected by
+/// [`render_identicon`](crate::render_identicon).
+///
+/// The reduction follows how jdenticon treats hex hashes of arbitrary length: the shapes and
+/// colors are chosen from the leading hex digits of the hash, and the hue from its last 7 hex
+/// digits. Specifically, the result consists of:
+///
+/// * the first 33 nibbles (hex digits) of the digest, padded with zero nibbles if the digest is
+///   shorter than that, followed by
+/// * the last 7 nibbles of the digest.
+///
+/// 20-byte digests, such as SHA-1, are left unchanged. This means that an identicon generated from
+/// a digest reduced this way is the same as the one jdenticon generates from the hex encoding of
+/// that digest.
+///
+/// This rule is stable and will not change between versions of rdenticon.
+///
+/// # Panics
+/// * If the digest is shorter than 6 bytes, which jdenticon does not accept as a hash either.
+#[must_use]
+pub fn reduce_digest(digest: &[u8]) -> [u8; 20] {
+    assert!(digest.len() >= 6, "digest must be at least 6 bytes long");
+
+    let nibble_count = digest.len() * 2;
+    let nibble = |i: usize| {
+        digest
+            .get(i / 2)
+            .map_or(0, |byte| if i & 1 == 0 { byte >> 4 } else { byte & 0x0f })
+    };
+
+    let mut out = [0; 20];
+    for i in 0..40 {
+        let value = if i < 33 {
+            nibble(i)
+        } else {
+            nibble(nibble_count + i - 40)
+        };
+        out[i / 2] |= if i & 1 == 0 { value << 4 } else { value };
+    }
+    out
+}
@@ -1,4 +1,4 @@
-use ril::Rgb;
+use ril::{Rgb, Rgba};
 
 fn hsl_to_raw_rgbf(h: f64, s: f64, l: f64) -> (f64, f64, f64) {
     // Optimize for grayscale
@@ -53,3 +53,99 @@ pub fn corrected_hsl_to_rgb(h: f64, s: f64, l: f64) -> Rgb {
 
     hsl_to_rgb(h, s, l)
 }
+
+/// Converts a color channel in the range `[0.0, 255.0]` to a byte, truncating it the same way
+/// jdenticon does.
+fn truncate_channel(value: f64) -> u8 {
+    if value < 0.0 {
+        0
+    } else if value >= 255.0 {
+        255
+    } else {
+        value as u8
+    }
+}
+
+fn hue_to_channel(m1: f64, m2: f64, h: f64) -> u8 {
+    let h = if h < 0.0 {
+        h + 6.0
+    } else if h > 6.0 {
+        h - 6.0
+    } else {
+        h
+    };
+
+    truncate_channel(
+        255.0
+            * if h < 1.0 {
+                m1 + (m2 - m1) * h
+            } else if h < 3.0 {
+                m2
+            } else if h < 4.0 {
+                m1 + (m2 - m1) * (4.0 - h)
+            } else {
+                m1
+            },
+    )
+}
+
+/// Converts an HSL color to an RGB color exactly the way jdenticon does, truncating rather than
+/// rounding each channel.
+///
+/// Unlike [`hsl_to_rgb`], `h` is a fraction of a full turn within the range `[0.0, 1.0]`.
+pub fn jdenticon_hsl_to_rgb(h: f64, s: f64, l: f64) -> Rgb {
+    if s == 0.0 {
+        let v = truncate_channel(l * 255.0);
+        return Rgb::new(v, v, v);
+    }
+
+    let m2 = if l <= 0.5 {
+        l * (s + 1.0)
+    } else {
+        l + s - l * s
+    };
+    let m1 = l * 2.0 - m2;
+
+    Rgb::new(
+        hue_to_channel(m1, m2, h * 6.0 + 2.0),
+        hue_to_channel(m1, m2, h * 6.0),
+        hue_to_channel(m1, m2, h * 6.0 - 2.0),
+    )
+}
+
+/// Like [`HSL_CORRECTORS`], but indexed by the rounded sextant of the hue as jdenticon does.
+const JDENTICON_HSL_CORRECTORS: [f64; 7] = [0.55, 0.5, 0.5, 0.46, 0.6, 0.55, 0.55];
+
+/// Converts an HSL color to an RGB color exactly the way jdenticon does, correcting for lightness
+/// for dark hues.
+///
+/// Unlike [`corrected_hsl_to_rgb`], `h` is a fraction of a full turn within the range
+/// `[0.0, 1.0]`.
+pub fn jdenticon_corrected_hsl_to_rgb(h: f64, s: f64, l: f64) -> Rgb {
+    let corrector = JDENTICON_HSL_CORRECTORS[(h * 6.0 + 0.5) as usize];
+    let l = if l < 0.5 {
+        l * corrector * 2.0
+    } else {
+        corrector + (l - 0.5) * (1.0 - corrector) * 2.0
+    };
+
+    jdenticon_hsl_to_rgb(h, s, l)
+}
+
+/// Mirrors the HSL lightness of a color around `0.5`, so that a lightness of `l` becomes `1 - l`
+/// while the hue, saturation and alpha are kept.
+///
+pub fn mirror_hsl_lightness(color: Rgba) -> Rgba {
+    let max = color.r.max(color.g).max(color.b);
+    let min = color.r.min(color.g).min(color.b);
+    // Every channel is shifted by `255 - max - min`, ordered so that it cannot overflow since
+    // `min <= value <= max`
+    let channel = |value: u8| 255 - max + (value - min);
+
+    Rgba::new(
+        channel(color.r),
+        channel(color.g),
+        channel(color.b),
+        color.a,
+    )
+}
@@ -0,0 +1,77 @@
+use std::borrow::Cow;
+
+/// A value that an identicon can be generated for.
+///
+/// Every input has a canonical byte encoding, which is what gets hashed. Encodings are stable and
+/// chosen so that they are easy to reproduce in other languages:
+///
+/// * Strings are encoded as UTF-8. This is what jdenticon hashes as well.
+/// * Byte slices, arrays and vectors are used as-is. This is suited for binary identifiers such as
+///   public keys.
+/// * Integers are encoded as their decimal representation, for example `42` is encoded the same
+///   as `"42"` and `-1` the same as `"-1"`. This matches how jdenticon hashes numbers.
+/// * UUIDs are encoded as their lowercase hyphenated representation, for example
+///   `"67e55044-10b1-426f-9247-bb680e5fe0c8"`. Requires the `uuid` feature.
+///
+/// To generate an identicon from the raw 16 bytes of a UUID instead, pass `uuid.as_bytes()`.
+pub trait IdenticonInput {
+    /// Returns the canonical byte encoding of this input.
+    fn identicon_bytes(&self) -> Cow<'_, [u8]>;
+}
+
+impl<T: IdenticonInput + ?Sized> IdenticonInput for &T {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        (**self).identicon_bytes()
+    }
+}
+
+impl IdenticonInput for str {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl IdenticonInput for String {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self.as_bytes())
+    }
+}
+
+impl IdenticonInput for [u8] {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl<const N: usize> IdenticonInput for [u8; N] {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+impl IdenticonInput for Vec<u8> {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Borrowed(self)
+    }
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl IdenticonInput for $t {
+                fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+                    Cow::Owned(self.to_string().into_bytes())
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "uuid")]
+impl IdenticonInput for uuid::Uuid {
+    fn identicon_bytes(&self) -> Cow<'_, [u8]> {
+        Cow::Owned(self.hyphenated().to_string().into_bytes())
+    }
+}
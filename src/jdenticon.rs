@@ -0,0 +1,299 @@
+//! A strict port of jdenticon's shape geometry and color theme, used when
+//! [`Config::jdenticon_compat`] is enabled.
+//!
+//! Unlike the default renderer, jdenticon positions shapes within a cell using fractional
+//! coordinates, truncates only where jdenticon does, and derives its colors with its own HSL
+//! conversion. This module mirrors `iconGenerator.js`, `shapes.js` and `graphics.js` from
+//! jdenticon 3.x as closely as possible, so that SVG output matches jdenticon's `toSvg`.
+
+use crate::{
+    hsl::jdenticon_corrected_hsl_to_rgb, CellSpec, Config, Palette, Renderer, ShapeGroup, ShapeKind,
+};
+use std::ops::RangeInclusive;
+
+impl Config {
+    /// Retrieves a hue allowed by the configured hues the same way jdenticon's `colorTheme` does.
+    /// Hues are fractions of a full turn in the range `[0.0, 1.0]`.
+    pub(crate) fn jdenticon_resolve_hue(&self, hue: f64) -> f64 {
+        self.hues
+            .get((0.999 * hue * self.hues.len() as f64) as usize)
+            .map_or(hue, |hue| (hue / 360.0 % 1.0 + 1.0) % 1.0)
+    }
+
+    /// Retrieves a set of color candidates the same way jdenticon's `colorTheme` does. The hue is
+    /// expected to be a fraction of a full turn in the range `[0.0, 1.0]`.
+    pub(crate) fn jdenticon_color_candidates(&self, hue: f64) -> Palette {
+        let hue = self.jdenticon_resolve_hue(hue);
+
+        let lightness = |range: &RangeInclusive<f64>, value: f64| {
+            let lightness = (range.start() + value * (range.end() - range.start())).clamp(0.0, 1.0);
+            if self.mirror_lightness {
+                1.0 - lightness
+            } else {
+                lightness
+            }
+        };
+        let color = |saturation: f64, range: &RangeInclusive<f64>, value: f64| {
+            jdenticon_corrected_hsl_to_rgb(hue, saturation, lightness(range, value)).into_rgba()
+        };
+
+        Palette {
+            light_gray: color(self.grayscale_saturation, &self.grayscale_lightness, 1.0),
+            dark_gray: color(self.grayscale_saturation, &self.grayscale_lightness, 0.0),
+            light_color: color(self.color_saturation, &self.color_lightness, 1.0),
+            mid_color: color(self.color_saturation, &self.color_lightness, 0.5),
+            dark_color: color(self.color_saturation, &self.color_lightness, 0.0),
+        }
+    }
+}
+
+/// jdenticon's `Transform`, which works with fractional coordinates.
+#[derive(Copy, Clone, Default)]
+struct Transform {
+    x: f64,
+    y: f64,
+    size: f64,
+    rotation: u8,
+}
+
+impl Transform {
+    fn transform(&self, (x, y): (f64, f64), (w, h): (f64, f64)) -> (f64, f64) {
+        let right = self.x + self.size;
+        let bottom = self.y + self.size;
+
+        match self.rotation {
+            1 => (right - y - h, self.y + x),
+            2 => (right - x - w, bottom - y - h),
+            3 => (self.x + y, bottom - x - w),
+            _ /* 0 */ => (self.x + x, self.y + y),
+        }
+    }
+}
+
+/// jdenticon's `Graphics`, which draws shapes relative to the current cell.
+struct Graphics<'a> {
+    renderer: &'a mut dyn Renderer,
+    transform: Transform,
+}
+
+impl Graphics<'_> {
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool) {
+        let mut points = points
+            .iter()
+            .map(|&point| self.transform.transform(point, (0.0, 0.0)))
+            .collect::<Vec<_>>();
+        if invert {
+            points.reverse();
+        }
+
+        self.renderer.polygon(&points, invert);
+    }
+
+    fn circle(&mut self, x: f64, y: f64, size: f64, invert: bool) {
+        let top_left = self.transform.transform((x, y), (size, size));
+        self.renderer.circle(top_left, size, invert);
+    }
+
+    fn rectangle(&mut self, x: f64, y: f64, w: f64, h: f64, invert: bool) {
+        self.polygon(&[(x, y), (x + w, y), (x + w, y + h), (x, y + h)], invert);
+    }
+
+    fn triangle(&mut self, x: f64, y: f64, w: f64, h: f64, rotation: usize, invert: bool) {
+        let mut points = vec![(x + w, y), (x + w, y + h), (x, y + h), (x, y)];
+        points.remove(rotation % 4);
+        self.polygon(&points, invert);
+    }
+
+    fn rhombus(&mut self, x: f64, y: f64, w: f64, h: f64, invert: bool) {
+        self.polygon(
+            &[
+                (x + w / 2.0, y),
+                (x + w, y + h / 2.0),
+                (x + w / 2.0, y + h),
+                (x, y + h / 2.0),
+            ],
+            invert,
+        );
+    }
+}
+
+/// Draws a shape with the given index into the current cell.
+type ShapeFn = fn(&mut Graphics, f64, u8, usize);
+
+fn outer_shape(g: &mut Graphics, cell: f64, shape_index: u8, _position_index: usize) {
+    match shape_index % 4 {
+        0 => g.triangle(0.0, 0.0, cell, cell, 0, false),
+        1 => g.triangle(0.0, cell / 2.0, cell, cell / 2.0, 0, false),
+        2 => g.rhombus(0.0, 0.0, cell, cell, false),
+        _ /* 3 */ => {
+            let m = cell / 6.0;
+            g.circle(m, m, cell - 2.0 * m, false);
+        }
+    }
+}
+
+fn center_shape(g: &mut Graphics, cell: f64, shape_index: u8, position_index: usize) {
+    match shape_index % 14 {
+        0 => {
+            let k = cell * 0.42;
+            g.polygon(
+                &[
+                    (0.0, 0.0),
+                    (cell, 0.0),
+                    (cell, cell - k * 2.0),
+                    (cell - k, cell),
+                    (0.0, cell),
+                ],
+                false,
+            );
+        }
+        1 => {
+            let w = (cell * 0.5).trunc();
+            let h = (cell * 0.8).trunc();
+            g.triangle(cell - w, 0.0, w, h, 2, false);
+        }
+        2 => {
+            let w = (cell / 3.0).trunc();
+            g.rectangle(w, w, cell - w, cell - w, false);
+        }
+        3 => {
+            let inner = cell * 0.1;
+            // Use fixed outer border widths in small icons to ensure the border is drawn
+            let outer = if cell < 6.0 {
+                1.0
+            } else if cell < 8.0 {
+                2.0
+            } else {
+                (cell * 0.25).trunc()
+            };
+            let inner = if inner > 1.0 {
+                inner.trunc()
+            } else if inner > 0.5 {
+                1.0
+            } else {
+                inner
+            };
+
+            g.rectangle(
+                outer,
+                outer,
+                cell - inner - outer,
+                cell - inner - outer,
+                false,
+            );
+        }
+        4 => {
+            let m = (cell * 0.15).trunc();
+            let w = (cell * 0.5).trunc();
+            g.circle(cell - w - m, cell - w - m, w, false);
+        }
+        5 => {
+            let inner = cell * 0.1;
+            let outer = inner * 4.0;
+            // Align edge to nearest pixel in large icons
+            let outer = if outer > 3.0 { outer.trunc() } else { outer };
+
+            g.rectangle(0.0, 0.0, cell, cell, false);
+            g.polygon(
+                &[
+                    (outer, outer),
+                    (cell - inner, outer),
+                    (outer + (cell - outer - inner) / 2.0, cell - inner),
+                ],
+                true,
+            );
+        }
+        6 => g.polygon(
+            &[
+                (0.0, 0.0),
+                (cell, 0.0),
+                (cell, cell * 0.7),
+                (cell * 0.4, cell * 0.4),
+                (cell * 0.7, cell),
+                (0.0, cell),
+            ],
+            false,
+        ),
+        7 | 11 => g.triangle(cell / 2.0, cell / 2.0, cell / 2.0, cell / 2.0, 3, false),
+        8 => {
+            g.rectangle(0.0, 0.0, cell, cell / 2.0, false);
+            g.rectangle(0.0, cell / 2.0, cell / 2.0, cell / 2.0, false);
+            g.triangle(cell / 2.0, cell / 2.0, cell / 2.0, cell / 2.0, 1, false);
+        }
+        9 => {
+            let inner = cell * 0.14;
+            // Use fixed outer border widths in small icons to ensure the border is drawn
+            let outer = if cell < 4.0 {
+                1.0
+            } else if cell < 6.0 {
+                2.0
+            } else {
+                (cell * 0.35).trunc()
+            };
+            let inner = if cell < 8.0 { inner } else { inner.trunc() };
+
+            g.rectangle(0.0, 0.0, cell, cell, false);
+            g.rectangle(
+                outer,
+                outer,
+                cell - outer - inner,
+                cell - outer - inner,
+                true,
+            );
+        }
+        10 => {
+            let inner = cell * 0.12;
+            let outer = inner * 3.0;
+
+            g.rectangle(0.0, 0.0, cell, cell, false);
+            g.circle(outer, outer, cell - inner - outer, true);
+        }
+        12 => {
+            let m = cell * 0.25;
+
+            g.rectangle(0.0, 0.0, cell, cell, false);
+            g.rhombus(m, m, cell - m, cell - m, true);
+        }
+        _ /* 13 */ => {
+            if position_index == 0 {
+                let m = cell * 0.4;
+                let w = cell * 1.2;
+                g.circle(m, m, w, false);
+            }
+        }
+    }
+}
+
+/// Renders the groups of shapes of the identicon with jdenticon's geometry.
+pub(crate) fn render_shapes(
+    groups: &[ShapeGroup],
+    renderer: &mut dyn Renderer,
+    offset: (u32, u32),
+    cell: u32,
+) {
+    let mut g = Graphics {
+        renderer,
+        transform: Transform::default(),
+    };
+    let (offset, cell) = ((offset.0 as f64, offset.1 as f64), cell as f64);
+
+    for group in groups {
+        let (shape_fn, shape_index): (ShapeFn, u8) = match group.shape {
+            ShapeKind::Outer(index) => (outer_shape, index),
+            ShapeKind::Center(index) => (center_shape, index),
+        };
+
+        g.renderer.begin_shape(group.color);
+        for (i, &CellSpec { x, y, rotation }) in group.cells.iter().enumerate() {
+            g.transform = Transform {
+                x: offset.0 + x as f64 * cell,
+                y: offset.1 + y as f64 * cell,
+                size: cell,
+                rotation: rotation % 4,
+            };
+
+            shape_fn(&mut g, cell, shape_index, i);
+        }
+        g.renderer.end_shape();
+    }
+}
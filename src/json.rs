@@ -0,0 +1,151 @@
+use crate::{
+    config::{format_hex_color, parse_hex_color},
+    Config, ConfigBuilderError, Rgba,
+};
+use serde::{Deserialize, Serialize};
+
+/// jdenticon's configuration object, as exported by its web configurator and accepted by
+/// `jdenticon.configure`, `jdenticon_config` and the `data-jdenticon-config` attribute.
+#[derive(Default, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JdenticonConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hues: Option<Hues>,
+    #[serde(default)]
+    lightness: Lightness,
+    #[serde(default)]
+    saturation: Saturation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    back_color: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    padding: Option<f64>,
+}
+
+/// jdenticon accepts either a single hue or an array of hues.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum Hues {
+    Single(f64),
+    Many(Vec<f64>),
+}
+
+#[derive(Default, Deserialize, Serialize)]
+struct Lightness {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    color: Option<[f64; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    grayscale: Option<[f64; 2]>,
+}
+
+/// jdenticon accepts either a single color saturation or an object with both saturations.
+#[derive(Deserialize, Serialize)]
+#[serde(untagged)]
+enum Saturation {
+    Single(f64),
+    Both {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        color: Option<f64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        grayscale: Option<f64>,
+    },
+}
+
+impl Default for Saturation {
+    fn default() -> Self {
+        Self::Both {
+            color: None,
+            grayscale: None,
+        }
+    }
+}
+
+impl Config {
+    /// Parses a jdenticon configuration object, such as the JSON exported by the jdenticon web
+    /// configurator or the value of a `data-jdenticon-config` attribute.
+    ///
+    /// The `hues`, `lightness.color`, `lightness.grayscale`, `saturation.color`,
+    /// `saturation.grayscale`, `backColor` and `padding` keys are mapped to the corresponding
+    /// [`Config`] fields. A single number for `saturation` sets the color saturation, like
+    /// jdenticon does. Hues are normalized to `[0.0, 360.0)` like jdenticon does. Missing keys
+    /// take jdenticon's defaults, which match [`Config::default`] except that the background is
+    /// [`Rgba::transparent`]. Unknown keys are ignored.
+    ///
+    /// This requires the `json` feature.
+    ///
+    /// # Errors
+    /// * If the input is not a valid jdenticon configuration object.
+    /// * If `backColor` is not a hex color.
+    /// * If the resulting configuration is invalid. See [`Config::validate`] for more information.
+    pub fn from_jdenticon_json(json: &str) -> Result<Self, ConfigBuilderError> {
+        let raw: JdenticonConfig = serde_json::from_str(json)
+            .map_err(|err| ConfigBuilderError::MalformedJson(err.to_string()))?;
+        let default = Self::default();
+
+        let hues = match raw.hues {
+            Some(Hues::Single(hue)) => vec![hue],
+            Some(Hues::Many(hues)) => hues,
+            None => Vec::new(),
+        };
+        let (color_saturation, grayscale_saturation) = match raw.saturation {
+            Saturation::Single(color) => (Some(color), None),
+            Saturation::Both { color, grayscale } => (color, grayscale),
+        };
+        let background_color = match raw.back_color {
+            Some(color) => {
+                parse_hex_color(&color).ok_or(ConfigBuilderError::MalformedColor(color))?
+            }
+            None => Rgba::transparent(),
+        };
+
+        let config = Self {
+            hues: hues
+                .into_iter()
+                .map(|hue| hue.rem_euclid(360.0) % 360.0)
+                .collect(),
+            color_lightness: raw
+                .lightness
+                .color
+                .map_or(default.color_lightness, |[start, end]| start..=end),
+            grayscale_lightness: raw
+                .lightness
+                .grayscale
+                .map_or(default.grayscale_lightness, |[start, end]| start..=end),
+            color_saturation: color_saturation.unwrap_or(default.color_saturation),
+            grayscale_saturation: grayscale_saturation.unwrap_or(default.grayscale_saturation),
+            background_color,
+            padding: raw.padding.unwrap_or(default.padding),
+            ..default
+        };
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    /// Serializes the jdenticon-compatible fields of this configuration to a jdenticon
+    /// configuration object. See [`Config::from_jdenticon_json`] for the mapping between keys and
+    /// fields. Options without a jdenticon equivalent, such as the dimensions, grid size,
+    /// palettes and [`Config::jdenticon_compat`], are not included.
+    ///
+    /// This requires the `json` feature.
+    #[must_use]
+    pub fn to_jdenticon_json(&self) -> String {
+        let raw = JdenticonConfig {
+            hues: (!self.hues.is_empty()).then(|| Hues::Many(self.hues.clone())),
+            lightness: Lightness {
+                color: Some([*self.color_lightness.start(), *self.color_lightness.end()]),
+                grayscale: Some([
+                    *self.grayscale_lightness.start(),
+                    *self.grayscale_lightness.end(),
+                ]),
+            },
+            saturation: Saturation::Both {
+                color: Some(self.color_saturation),
+                grayscale: Some(self.grayscale_saturation),
+            },
+            back_color: Some(format_hex_color(self.background_color)),
+            padding: Some(self.padding),
+        };
+
+        serde_json::to_string(&raw).expect("jdenticon config is always serializable")
+    }
+}
@@ -0,0 +1,84 @@
+//! Cell layouts of identicon grids.
+//!
+//! A grid of `n` by `n` cells is made of concentric rings, each split into corner and side
+//! cells, around a center of 2 by 2 cells, or a single cell if `n` is odd. Every cell is given as
+//! `(x, y, rotation)`, where `rotation` is the amount of quarter turns its shape is rotated by
+//! relative to the other cells of its group. Cells that are 180 degrees apart are always 2
+//! quarter turns apart, which keeps identicons point-symmetric.
+//!
+//! For the default 4x4 grid, these are the same layouts jdenticon uses.
+
+/// A cell of the grid, as `(x, y, rotation)`.
+pub(crate) type Cell = (u32, u32, u8);
+
+/// Returns the rotation of the `k`th cell of the left edge of a ring, counting from 1.
+const fn left_rotation(k: u32) -> u8 {
+    if k % 2 == 1 {
+        0
+    } else {
+        3
+    }
+}
+
+/// Returns the side cells of the ring of `len` by `len` cells whose top-left cell is at
+/// `(start, start)`.
+pub(crate) fn side_cells(start: u32, len: u32) -> impl Iterator<Item = Cell> + Clone {
+    let last = len - 1;
+    let inner = 1..last;
+
+    // Top edge from left to right, then the bottom edge from right to left
+    let top = inner
+        .clone()
+        .map(move |x| (start + x, start, ((x - 1) % 2) as u8));
+    let bottom = inner
+        .clone()
+        .map(move |k| (start + last - k, start + last, ((k - 1) % 2) as u8 + 2));
+
+    // Left and right edges from top to bottom, alternating which one comes first
+    let edges = inner.flat_map(move |y| {
+        let left = (start, start + y, left_rotation(y));
+        let right = (start + last, start + y, (left_rotation(last - y) + 2) % 4);
+        if y % 2 == 1 {
+            [left, right]
+        } else {
+            [right, left]
+        }
+    });
+
+    top.chain(bottom).chain(edges)
+}
+
+/// Returns the corner cells of the ring of `len` by `len` cells whose top-left cell is at
+/// `(start, start)`, clockwise from the top-left corner.
+pub(crate) const fn corner_cells(start: u32, len: u32) -> [Cell; 4] {
+    let end = start + len - 1;
+    [
+        (start, start, 0),
+        (end, start, 1),
+        (end, end, 2),
+        (start, end, 3),
+    ]
+}
+
+/// Returns the center cells of a grid of `grid_size` by `grid_size` cells, clockwise from the
+/// top-left cell.
+pub(crate) fn center_cells(grid_size: u32) -> impl Iterator<Item = Cell> + Clone {
+    let (start, end) = ((grid_size - 1) / 2, grid_size / 2);
+    let cells = [
+        (start, start, 0),
+        (end, start, 1),
+        (end, end, 2),
+        (start, end, 3),
+    ];
+
+    // Odd grids have a single center cell
+    cells.into_iter().take(if start == end { 1 } else { 4 })
+}
+
+/// Returns the rings of a grid of `grid_size` by `grid_size` cells from the outside in, as the
+/// index of their top-left cell along with their length in cells.
+pub(crate) fn rings(grid_size: u32) -> impl Iterator<Item = (u32, u32)> {
+    (0..)
+        .map(move |ring| (ring, grid_size - ring * 2))
+        .take_while(|&(_, len)| len > 2)
+}
@@ -0,0 +1,104 @@
+use crate::{hsl::corrected_hsl_to_rgb, ColorSpace};
+use ril::Rgb;
+
+/// The chroma that a saturation of `1.0` corresponds to in OKLCH.
+const OKLCH_MAX_CHROMA: f64 = 0.4;
+
+/// The chroma that a saturation of `1.0` corresponds to in CIELCh.
+const CIELCH_MAX_CHROMA: f64 = 150.0;
+
+/// Converts an OKLab color to linear sRGB.
+///
+/// From <https://bottosson.github.io/posts/oklab/>
+fn oklab_to_linear_srgb(l: f64, a: f64, b: f64) -> [f64; 3] {
+    let l_ = l + 0.396_337_777_4 * a + 0.215_803_757_3 * b;
+    let m_ = l - 0.105_561_345_8 * a - 0.063_854_172_8 * b;
+    let s_ = l - 0.089_484_177_5 * a - 1.291_485_548_0 * b;
+    let (l, m, s) = (l_.powi(3), m_.powi(3), s_.powi(3));
+
+    [
+        4.076_741_662_1 * l - 3.307_711_591_3 * m + 0.230_969_929_2 * s,
+        -1.268_438_004_6 * l + 2.609_757_401_1 * m - 0.341_319_396_5 * s,
+        -0.004_196_086_3 * l - 0.703_418_614_7 * m + 1.707_614_701_0 * s,
+    ]
+}
+
+/// Converts a CIELAB color with a D65 white point to linear sRGB. `l` is within the range
+/// `[0.0, 100.0]`.
+fn lab_to_linear_srgb(l: f64, a: f64, b: f64) -> [f64; 3] {
+    const DELTA: f64 = 6.0 / 29.0;
+    let f_inv = |t: f64| {
+        if t > DELTA {
+            t.powi(3)
+        } else {
+            3.0 * DELTA * DELTA * (t - 4.0 / 29.0)
+        }
+    };
+
+    let fy = (l + 16.0) / 116.0;
+    let x = 0.950_47 * f_inv(fy + a / 500.0);
+    let y = f_inv(fy);
+    let z = 1.088_83 * f_inv(fy - b / 200.0);
+
+    [
+        3.240_454_2 * x - 1.537_138_5 * y - 0.498_531_4 * z,
+        -0.969_266_0 * x + 1.876_010_8 * y + 0.041_556_0 * z,
+        0.055_643_4 * x - 0.204_025_9 * y + 1.057_225_2 * z,
+    ]
+}
+
+/// Applies the sRGB transfer function to a linear channel and converts it to a byte.
+fn encode_channel(value: f64) -> u8 {
+    let value = if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    };
+    (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Converts a cylindrical color to an RGB color, where `to_linear_srgb` converts from the
+/// rectangular form of its color space.
+///
+/// Colors outside of the sRGB gamut have their chroma reduced until they fit, which keeps their
+/// lightness and hue intact rather than clipping each channel.
+fn lch_to_rgb(l: f64, c: f64, h: f64, to_linear_srgb: fn(f64, f64, f64) -> [f64; 3]) -> Rgb {
+    const EPSILON: f64 = 1e-6;
+
+    let (sin, cos) = h.to_radians().sin_cos();
+    let linear = |c: f64| to_linear_srgb(l, c * cos, c * sin);
+    let in_gamut = |rgb: [f64; 3]| {
+        rgb.iter()
+            .all(|channel| (-EPSILON..=1.0 + EPSILON).contains(channel))
+    };
+
+    let mut rgb = linear(c);
+    if !in_gamut(rgb) {
+        // Binary search for the largest chroma within the gamut
+        let (mut low, mut high) = (0.0, c);
+        for _ in 0..24 {
+            let mid = (low + high) / 2.0;
+            if in_gamut(linear(mid)) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        rgb = linear(low);
+    }
+
+    let [r, g, b] = rgb.map(encode_channel);
+    Rgb::new(r, g, b)
+}
+
+impl ColorSpace {
+    /// Converts a color to RGB in this color space. `h` is in degrees, while `s` and `l` are
+    /// within the range `[0.0, 1.0]`. See [`ColorSpace`] for how they are interpreted.
+    pub(crate) fn to_rgb(self, h: f64, s: f64, l: f64) -> Rgb {
+        match self {
+            Self::Hsl => corrected_hsl_to_rgb(h, s, l),
+            Self::Oklch => lch_to_rgb(l, s * OKLCH_MAX_CHROMA, h, oklab_to_linear_srgb),
+            Self::Cielch => lch_to_rgb(l * 100.0, s * CIELCH_MAX_CHROMA, h, lab_to_linear_srgb),
+        }
+    }
+}
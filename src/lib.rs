@@ -5,35 +5,54 @@
     clippy::cast_sign_loss
 )]
 
+mod antialias;
+#[cfg(feature = "rayon")]
+mod batch;
+#[cfg(feature = "capi")]
+pub mod capi;
 mod config;
+mod contrast;
+mod describe;
+mod error;
+mod hash;
 mod hsl;
+mod input;
+mod jdenticon;
+#[cfg(feature = "json")]
+mod json;
+mod layout;
+mod lch;
+mod palette;
+mod renderer;
+#[cfg(feature = "serde")]
+mod serde_impl;
+mod shapes;
+mod spec;
+mod sprite;
+mod svg;
+mod theme;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+pub use antialias::CoverageRenderer;
+#[cfg(feature = "rayon")]
+pub use batch::*;
 pub use config::*;
-use hsl::corrected_hsl_to_rgb;
+pub use contrast::contrast_ratio;
+pub use describe::*;
+pub use error::*;
+pub use hash::*;
+pub use input::*;
+pub use palette::*;
+use renderer::StretchRenderer;
+pub use renderer::{BufferRenderer, ImageRenderer, Renderer};
 use ril::prelude::*;
 pub use ril::{self, ImageFormat};
-
-/// Colors used by an identicon.
-struct ColorCandidates {
-    light_gray: Rgba,
-    dark_gray: Rgba,
-    light_color: Rgba,
-    mid_color: Rgba,
-    dark_color: Rgba,
-}
-
-impl ColorCandidates {
-    #[inline]
-    const fn get_from_rotation_index(&self, index: usize) -> Rgba {
-        match index {
-            0 => self.dark_gray,
-            1 => self.mid_color,
-            2 => self.light_gray,
-            3 => self.light_color,
-            _ /* 4 */ => self.dark_color,
-        }
-    }
-}
+pub use shapes::*;
+pub use spec::*;
+pub use sprite::*;
+pub use svg::SvgRenderer;
+pub use theme::*;
 
 impl Config {
     /// Retrieves a hue allowed by the configured hues.
@@ -41,33 +60,65 @@ impl Config {
         if self.hues.is_empty() {
             hue
         } else {
-            self.hues[(hue / 360.0 * self.hues.len() as f64) as usize]
+            // The hue may be exactly 360.0, which would otherwise be out of bounds
+            let index = (hue / 360.0 * self.hues.len() as f64) as usize;
+            self.hues[index.min(self.hues.len() - 1)]
+        }
+    }
+
+    /// Retrieves one of the configured palettes, mirrored if [`Config::mirror_lightness`] is set.
+    /// The hue is expected to be a fraction of a full turn in the range `[0.0, 1.0]`, and there
+    /// must be at least one palette.
+    pub(crate) fn resolve_palette(&self, hue: f64) -> Palette {
+        let index = (hue * self.palettes.len() as f64) as usize;
+        let palette = self.palettes[index.min(self.palettes.len() - 1)];
+
+        if self.mirror_lightness {
+            palette.mirror_lightness()
+        } else {
+            palette
         }
     }
 
-    /// Retrieves a color lightness that conforms to the configured lightness range. The lightness
-    /// is expected to be in the range `[0.0, 1.0]`.
+    /// Retrieves a color lightness that conforms to the configured lightness range, mirrored if
+    /// [`Config::mirror_lightness`] is set. The lightness is expected to be in the range
+    /// `[0.0, 1.0]`.
     #[inline]
     pub(crate) fn resolve_color_lightness(&self, lightness: f64) -> f64 {
-        (self.color_lightness.end() - self.color_lightness.start())
-            .mul_add(lightness, *self.color_lightness.start())
+        let lightness = (self.color_lightness.end() - self.color_lightness.start())
+            .mul_add(lightness, *self.color_lightness.start());
+
+        if self.mirror_lightness {
+            1.0 - lightness
+        } else {
+            lightness
+        }
     }
 
-    /// Retrieves a grayscale lightness that conforms to the configured lightness range. The
-    /// lightness is expected to be in the range `[0.0, 1.0]`.
+    /// Retrieves a grayscale lightness that conforms to the configured lightness range, mirrored
+    /// if [`Config::mirror_lightness`] is set. The lightness is expected to be in the range
+    /// `[0.0, 1.0]`.
     #[inline]
     pub(crate) fn resolve_grayscale_lightness(&self, lightness: f64) -> f64 {
-        (self.grayscale_lightness.end() - self.grayscale_lightness.start())
-            .mul_add(lightness, *self.grayscale_lightness.start())
+        let lightness = (self.grayscale_lightness.end() - self.grayscale_lightness.start())
+            .mul_add(lightness, *self.grayscale_lightness.start());
+
+        if self.mirror_lightness {
+            1.0 - lightness
+        } else {
+            lightness
+        }
     }
 
     /// Retrieves a set of color candidates that conform to this configuration.
-    pub(crate) fn color_candidates(&self, hue: f64) -> ColorCandidates {
+    pub(crate) fn color_candidates(&self, hue: f64) -> Palette {
         let hue = self.resolve_hue(hue);
 
         macro_rules! resolve {
             ($s:ident, $l_meth:ident, $l_value:literal) => {{
-                corrected_hsl_to_rgb(hue, self.$s, self.$l_meth($l_value)).into_rgba()
+                self.color_space
+                    .to_rgb(hue, self.$s, self.$l_meth($l_value))
+                    .into_rgba()
             }};
             (@grayscale $l_value:literal) => {{
                 resolve!(grayscale_saturation, resolve_grayscale_lightness, $l_value)
@@ -77,7 +128,7 @@ impl Config {
             }};
         }
 
-        ColorCandidates {
+        Palette {
             light_gray: resolve!(@grayscale 1.0),
             dark_gray: resolve!(@grayscale 0.0),
             light_color: resolve!(@color 1.0),
@@ -109,61 +160,76 @@ impl Transform {
     }
 
     pub(crate) const fn transform(&self, (x, y): (u32, u32), (w, h): (u32, u32)) -> (u32, u32) {
+        // Saturate rather than underflow on shapes that are too large for very small cells
         match self.rotation {
             0 => (self.x + x, self.y + y),
-            1 => (self.right - y - h, self.y + x),
-            2 => (self.right - x - w, self.bottom - y - h),
-            _ /* 3 */ => (self.x + y, self.bottom - x - w),
+            1 => (self.right.saturating_sub(y + h), self.y + x),
+            2 => (
+                self.right.saturating_sub(x + w),
+                self.bottom.saturating_sub(y + h),
+            ),
+            _ /* 3 */ => (self.x + y, self.bottom.saturating_sub(x + w)),
         }
     }
 }
 
-struct ShapeRenderer<'a> {
-    image: &'a mut Image<Rgba>,
-    pub current_transform: Transform,
+/// Draws shapes into a cell of the grid. See [`ShapeSet`] for how to draw custom shapes.
+///
+/// Coordinates are in pixels relative to the top-left corner of the cell, and are rotated along
+/// with the cell. Shapes with `invert` set to `true` cut holes into the shapes drawn before them
+/// in the same cell.
+pub struct ShapeRenderer<'a> {
+    renderer: &'a mut dyn Renderer,
+    current_transform: Transform,
 }
 
 impl<'a> ShapeRenderer<'a> {
-    pub fn new(image: &'a mut Image<Rgba>) -> Self {
+    fn new(renderer: &'a mut dyn Renderer) -> Self {
         Self {
-            image,
+            renderer,
             current_transform: Transform::default(),
         }
     }
 
+    /// Fills a polygon with the given vertices.
     pub fn polygon(
         &mut self,
-        color: Rgba,
         points: impl IntoIterator<Item = (u32, u32)>,
+        invert: bool,
     ) -> &mut Self {
-        let polygon = Polygon::from_vertices(
-            points
-                .into_iter()
-                .map(|pos| self.current_transform.transform(pos, (0, 0))),
-        )
-        .with_fill(color);
+        let mut points = points
+            .into_iter()
+            .map(|pos| {
+                let (x, y) = self.current_transform.transform(pos, (0, 0));
+                (x as f64, y as f64)
+            })
+            .collect::<Vec<_>>();
+        if invert {
+            points.reverse();
+        }
 
-        self.image.draw(&polygon);
+        self.renderer.polygon(&points, invert);
         self
     }
 
-    pub fn circle(&mut self, color: Rgba, top_left: (u32, u32), diameter: u32) -> &mut Self {
+    /// Fills a circle whose bounding box starts at `top_left`.
+    pub fn circle(&mut self, top_left: (u32, u32), diameter: u32, invert: bool) -> &mut Self {
         let (x, y) = self
             .current_transform
             .transform(top_left, (diameter, diameter));
-        let circle = Ellipse::from_bounding_box(x, y, x + diameter, y + diameter).with_fill(color);
 
-        self.image.draw(&circle);
+        self.renderer
+            .circle((x as f64, y as f64), diameter as f64, invert);
         self
     }
 
-    // top left is top left of the bounding box
-    // this creates a right triangle
+    /// Fills a right triangle within the given bounding box. `ROTATION` selects the corner of
+    /// the bounding box that is left out, clockwise starting from the top-right corner.
     pub fn triangle<const ROTATION: usize>(
         &mut self,
-        color: Rgba,
         (x, y): (u32, u32),
         (w, h): (u32, u32),
+        invert: bool,
     ) -> &mut Self {
         let (a, b, c, d) = ((x + w, y), (x + w, y + h), (x, y + h), (x, y));
         let points = match ROTATION % 4 {
@@ -175,40 +241,37 @@ impl<'a> ShapeRenderer<'a> {
             _ => unsafe { std::hint::unreachable_unchecked() },
         };
 
-        self.polygon(color, points);
+        self.polygon(points, invert);
         self
     }
 
+    /// Fills an axis-aligned rectangle.
     pub fn rectangle(
         &mut self,
-        color: Rgba,
         top_left: (u32, u32),
         mut size: (u32, u32),
+        invert: bool,
     ) -> &mut Self {
         let (x, y) = self.current_transform.transform(top_left, size);
         if self.current_transform.rotation & 1 == 1 {
             std::mem::swap(&mut size.0, &mut size.1);
         }
 
-        let rect = Rectangle::new()
-            .with_position(x, y)
-            .with_size(size.0, size.1 + 1)
-            .with_fill(color);
-
-        self.image.draw(&rect);
+        self.renderer
+            .rectangle((x as f64, y as f64), (size.0 as f64, size.1 as f64), invert);
         self
     }
 
-    // top left is top left of the bounding box
-    pub fn rhombus(&mut self, color: Rgba, top_left: (u32, u32), size: (u32, u32)) -> &mut Self {
+    /// Fills a rhombus whose vertices touch the middle of each edge of the given bounding box.
+    pub fn rhombus(&mut self, top_left: (u32, u32), size: (u32, u32), invert: bool) -> &mut Self {
         self.polygon(
-            color,
             [
                 (top_left.0 + size.0 / 2, top_left.1),
                 (top_left.0 + size.0, top_left.1 + size.1 / 2),
                 (top_left.0 + size.0 / 2, top_left.1 + size.1),
                 (top_left.0, top_left.1 + size.1 / 2),
             ],
+            invert,
         )
     }
 }
@@ -244,71 +307,26 @@ fn hash_substring_u32<const LEN: usize>(nibbles: &[u8; 40], start: usize) -> u32
     u32::from_be_bytes(bytes)
 }
 
-#[allow(clippy::too_many_arguments)]
-fn render_shape(
-    hash: &[u8; 40],
-    shape_index: usize,
-    rotation_index: Option<usize>,
-    renderer: &mut ShapeRenderer,
-    color: Rgba,
-    background_color: Rgba,
-    cell_offset: u32,
-    cell_size: u32,
-    render_fn: impl Fn(&mut ShapeRenderer, Rgba, Rgba, u32, u8, usize),
-    render_positions: impl IntoIterator<Item = (u32, u32)>,
-) {
-    let mut rotation = rotation_index.map(|idx| hash[idx]).unwrap_or_default();
-    let shape_index = hash[shape_index];
-
-    render_positions
-        .into_iter()
-        .enumerate()
-        .for_each(|(i, (x, y))| {
-            renderer.current_transform = Transform::new(
-                cell_offset + x * cell_size,
-                cell_offset + y * cell_size,
-                cell_size,
-                rotation % 4,
-            );
-            rotation += 1;
-
-            render_fn(renderer, color, background_color, cell_size, shape_index, i);
-        });
-}
-
-fn render_outer(
-    renderer: &mut ShapeRenderer,
-    color: Rgba,
-    _background_color: Rgba,
-    cell_size: u32,
-    shape_index: u8,
-    _position_index: usize,
-) {
-    match shape_index % 4 {
-        0 => renderer.triangle::<0>(color, (0, 0), (cell_size, cell_size)),
-        1 => renderer.triangle::<0>(color, (0, cell_size / 2), (cell_size, cell_size / 2)),
-        2 => renderer.rhombus(color, (0, 0), (cell_size, cell_size)),
+fn render_outer(renderer: &mut ShapeRenderer, cell: ShapeCell, shape_index: u8) {
+    let cell_size = cell.size;
+    match shape_index {
+        0 => renderer.triangle::<0>((0, 0), (cell_size, cell_size), false),
+        1 => renderer.triangle::<0>((0, cell_size / 2), (cell_size, cell_size / 2), false),
+        2 => renderer.rhombus((0, 0), (cell_size, cell_size), false),
         _ /* 3 */ => {
             let m = cell_size / 6;
-            renderer.circle(color, (m, m), cell_size - 2 * m)
+            renderer.circle((m, m), cell_size - 2 * m, false)
         },
     };
 }
 
 #[allow(clippy::too_many_lines)]
-fn render_center(
-    renderer: &mut ShapeRenderer,
-    color: Rgba,
-    background_color: Rgba,
-    cell_size: u32,
-    shape_index: u8,
-    position_index: usize,
-) {
-    match shape_index % 14 {
+fn render_center(renderer: &mut ShapeRenderer, cell: ShapeCell, shape_index: u8) {
+    let cell_size = cell.size;
+    match shape_index {
         0 => {
             let k = (cell_size as f64 * 0.42) as u32;
             renderer.polygon(
-                color,
                 [
                     (0, 0),
                     (cell_size, 0),
@@ -316,19 +334,20 @@ fn render_center(
                     (cell_size - k, cell_size),
                     (0, cell_size),
                 ],
+                false,
             );
         }
         1 => {
             let w = cell_size / 2;
             let h = (cell_size as f64 * 0.8) as u32;
 
-            renderer.triangle::<2>(color, (cell_size - w, 0), (w, h));
+            renderer.triangle::<2>((cell_size - w, 0), (w, h), false);
         }
         2 => {
             let w = cell_size / 3;
             let dw = cell_size - w;
 
-            renderer.rectangle(color, (w, w), (dw, dw));
+            renderer.rectangle((w, w), (dw, dw), false);
         }
         3 => {
             let inner = cell_size as f64 / 10.0;
@@ -343,30 +362,30 @@ fn render_center(
             };
 
             let inner = if inner > 1.0 { inner as u32 } else { 1 };
-            let p = cell_size - inner - outer;
+            let p = cell_size.saturating_sub(inner + outer);
 
-            renderer.rectangle(color, (outer, outer), (p, p));
+            renderer.rectangle((outer, outer), (p, p), false);
         }
         4 => {
             let m = (cell_size as f64 * 0.15) as u32;
             let w = cell_size / 2;
             let p = cell_size - w - m;
 
-            renderer.circle(color, (p, p), w);
+            renderer.circle((p, p), w, false);
         }
         5 => {
             let inner = cell_size / 10;
             let outer = (cell_size as f64 * 0.4) as u32;
 
             renderer
-                .rectangle(color, (0, 0), (cell_size, cell_size))
+                .rectangle((0, 0), (cell_size, cell_size), false)
                 .polygon(
-                    background_color,
                     [
                         (outer, outer),
                         (cell_size - inner, outer),
                         (outer + (cell_size - outer - inner) / 2, cell_size - inner),
                     ],
+                    true,
                 );
         }
         6 => {
@@ -375,7 +394,6 @@ fn render_center(
             let seven_tenths = tenth * 7;
 
             renderer.polygon(
-                color,
                 [
                     (0, 0),
                     (cell_size, 0),
@@ -384,21 +402,22 @@ fn render_center(
                     (seven_tenths, cell_size),
                     (0, cell_size),
                 ],
+                false,
             );
         }
         7 | 11 => {
             let half_cell = cell_size / 2;
             let diff = cell_size - half_cell;
-            renderer.triangle::<3>(color, (half_cell, half_cell), (diff, diff));
+            renderer.triangle::<3>((half_cell, half_cell), (diff, diff), false);
         }
         8 => {
             let half_cell = cell_size / 2;
             let diff = cell_size - half_cell;
 
             renderer
-                .rectangle(color, (0, 0), (cell_size, diff))
-                .rectangle(color, (0, half_cell), (diff, diff))
-                .triangle::<1>(color, (half_cell, half_cell), (diff, diff));
+                .rectangle((0, 0), (cell_size, diff), false)
+                .rectangle((0, half_cell), (diff, diff), false)
+                .triangle::<1>((half_cell, half_cell), (diff, diff), false);
         }
         9 => {
             let inner = (cell_size as f64 * 0.14) as u32;
@@ -410,10 +429,10 @@ fn render_center(
                 (cell_size as f64 * 0.35) as u32
             };
 
-            let p = cell_size - outer - inner;
+            let p = cell_size.saturating_sub(outer + inner);
             renderer
-                .rectangle(color, (0, 0), (cell_size, cell_size))
-                .rectangle(background_color, (outer, outer), (p, p));
+                .rectangle((0, 0), (cell_size, cell_size), false)
+                .rectangle((outer, outer), (p, p), true);
         }
         10 => {
             let inner = cell_size as f64 * 0.12;
@@ -421,28 +440,154 @@ fn render_center(
             let inner = inner as u32;
 
             renderer
-                .rectangle(color, (0, 0), (cell_size, cell_size))
-                .circle(background_color, (outer, outer), cell_size - inner - outer);
+                .rectangle((0, 0), (cell_size, cell_size), false)
+                .circle((outer, outer), cell_size - inner - outer, true);
         }
         12 => {
             let m = cell_size / 4;
             let p = cell_size - m;
 
             renderer
-                .rectangle(color, (0, 0), (cell_size, cell_size))
-                .rectangle(background_color, (m, m), (p, p));
+                .rectangle((0, 0), (cell_size, cell_size), false)
+                .rectangle((m, m), (p, p), true);
         }
-        13 if position_index == 0 => {
+        // Odd grids have a single center cell, which the circle is inscribed in instead
+        13 if cell.count == 1 => {
+            renderer.circle((0, 0), cell_size, false);
+        }
+        13 if cell.index == 0 => {
             let fcell = cell_size as f64;
             let m = (fcell * 0.4) as u32;
             let w = (fcell * 1.2) as u32;
 
-            renderer.circle(color, (m, m), w);
+            renderer.circle((m, m), w, false);
         }
         _ => (),
     }
 }
 
+/// Renders an identicon for the given hash onto a custom [`Renderer`]. The hash is strictly
+/// 20-bytes long, see [`render_identicon`] for more information.
+///
+/// This is how [`render_identicon`] and [`render_identicon_svg`] are implemented, and can be used
+/// to plug in other backends, such as a canvas or a PDF document, while reusing the shape
+/// geometry of rdenticon. See [`Renderer`] for the order in which its methods are called.
+pub fn render_identicon_with(hash: [u8; 20], config: &Config, renderer: &mut impl Renderer) {
+    render_spec_with(&IdenticonSpec::new(hash, config), config, renderer);
+}
+
+/// Renders an [`IdenticonSpec`] onto a custom [`Renderer`].
+///
+/// The size, padding, alignment and background of the icon are taken from the configuration,
+/// while everything the hash decides is taken from the spec. Shapes are drawn from
+/// [`Config::shapes`], or with jdenticon's geometry if [`Config::jdenticon_compat`] is set. Groups
+/// whose shape is not within the shape set are skipped.
+pub fn render_spec_with(spec: &IdenticonSpec, config: &Config, renderer: &mut impl Renderer) {
+    let (width, height) = (config.width, config.height);
+    let (padding_x, padding_y) = (
+        (config.padding * width as f64).round() as u32,
+        (config.padding * height as f64).round() as u32,
+    );
+    let (area_width, area_height) = (
+        width.saturating_sub(padding_x * 2),
+        height.saturating_sub(padding_y * 2),
+    );
+
+    renderer.background(config.background_color);
+
+    // The side length of a square grid that fits within the padded area
+    let size = area_width.min(area_height);
+    match config.alignment {
+        Alignment::Center => {
+            let origin = (
+                padding_x + (area_width - size) / 2,
+                padding_y + (area_height - size) / 2,
+            );
+            render_grid(spec, config, renderer, origin, size);
+        }
+        Alignment::Fit => {
+            // Render a square grid as large as the longer axis, then scale it down along the
+            // shorter axis
+            let size = area_width.max(area_height);
+            let mut renderer = StretchRenderer {
+                renderer,
+                origin: (padding_x as f64, padding_y as f64),
+                scale: (
+                    area_width as f64 / size.max(1) as f64,
+                    area_height as f64 / size.max(1) as f64,
+                ),
+            };
+            render_grid(spec, config, &mut renderer, (0, 0), size);
+        }
+        Alignment::Tile => {
+            let count = area_width
+                .max(area_height)
+                .checked_div(size)
+                .unwrap_or_default()
+                .max(1);
+            let (step, mut origin) = if area_width >= area_height {
+                let start = padding_x + (area_width - size * count) / 2;
+                ((size, 0), (start, padding_y))
+            } else {
+                let start = padding_y + (area_height - size * count) / 2;
+                ((0, size), (padding_x, start))
+            };
+
+            for _ in 0..count {
+                render_grid(spec, config, renderer, origin, size);
+                origin = (origin.0 + step.0, origin.1 + step.1);
+            }
+        }
+    }
+}
+
+/// Renders the shapes of an identicon onto a square grid of `size` pixels whose top-left corner
+/// is at `origin`.
+fn render_grid(
+    spec: &IdenticonSpec,
+    config: &Config,
+    renderer: &mut dyn Renderer,
+    (x, y): (u32, u32),
+    size: u32,
+) {
+    let grid_size = spec.grid_size.max(1);
+    let cell = size / grid_size;
+    let offset = (
+        x + (size - cell * grid_size) / 2,
+        y + (size - cell * grid_size) / 2,
+    );
+
+    if config.jdenticon_compat {
+        jdenticon::render_shapes(&spec.groups, renderer, offset, cell);
+        return;
+    }
+
+    let mut renderer = ShapeRenderer::new(renderer);
+    for group in &spec.groups {
+        let shape = match group.shape {
+            ShapeKind::Outer(index) => config.shapes.outer.get(index as usize),
+            ShapeKind::Center(index) => config.shapes.center.get(index as usize),
+        };
+        let Some(&shape) = shape else {
+            continue;
+        };
+
+        renderer.renderer.begin_shape(group.color);
+        for (i, &CellSpec { x, y, rotation }) in group.cells.iter().enumerate() {
+            renderer.current_transform =
+                Transform::new(offset.0 + x * cell, offset.1 + y * cell, cell, rotation % 4);
+
+            let cell = ShapeCell {
+                size: cell,
+                index: i,
+                count: group.cells.len(),
+            };
+            shape(&mut renderer, cell);
+        }
+        renderer.renderer.end_shape();
+    }
+}
+
 /// Renders an identicon for the given hash. The hash is strictly 20-bytes long. If your hash is
 /// shorter, you should pad it. Similarly, if your hash is longer, you should truncate it.
 ///
@@ -453,98 +598,231 @@ fn render_center(
 /// Saving identicons to different encodings require different features to be enabled. By default,
 /// rdenticon enables the `ril/png` feature. If, for example, I wanted to save identicons as JPEGs,
 /// I would enable the `ril/jpeg` feature. See the [`ril`] crate for more information on features.
+///
+/// # Panics
+/// * If [`Config::width`] or [`Config::height`] is zero. See [`try_render_identicon`] for a
+///   non-panicking alternative.
 pub fn render_identicon(hash: [u8; 20], config: &Config) -> Image<Rgba> {
-    const SIDE_POSITIONS: [(u32, u32); 8] = [
-        (1, 0),
-        (2, 0),
-        (2, 3),
-        (1, 3),
-        (0, 1),
-        (3, 1),
-        (3, 2),
-        (0, 2),
-    ];
-    const CORNER_POSITIONS: [(u32, u32); 4] = [(0, 0), (3, 0), (3, 3), (0, 3)];
-    const CENTER_POSITIONS: [(u32, u32); 4] = [(1, 1), (2, 1), (2, 2), (1, 2)];
-
-    let mut image = Image::new(config.size, config.size, config.background_color);
-
-    let padding = (config.padding * config.size as f64).round() as u32;
-    let size = config.size - padding * 2;
-
-    let cell = size / 4;
-    let offset = padding + size / 2 - cell * 2;
-
-    let hash = into_nibbles(hash);
-    let hue = 360.0 * hash_substring_u32::<7>(&hash, 33) as f64 / 0xfffffff as f64;
-    let color_candidates = config.color_candidates(hue);
-
-    let mut selected_indices = [!0; 3];
-    // `.contains` optimization
-    macro_rules! contains_opt {
-        ($value:literal) => {{
-            selected_indices[0] == $value
-                || selected_indices[1] == $value
-                || selected_indices[2] == $value
-        }};
-    }
-
-    for i in 0..3 {
-        let index = hash[i + 8] % 5;
-        let index = match index {
-            0 | 4 if contains_opt!(0) || contains_opt!(4) => 1,
-            2 | 3 if contains_opt!(2) || contains_opt!(3) => 1,
-            _ => index,
-        };
+    let mut image = Image::new(config.width, config.height, config.background_color);
+    draw_identicon(hash, config, &mut image, (0, 0));
+
+    image
+}
+
+/// Draws an identicon onto an image at the given offset, with the anti-aliasing configured by
+/// [`Config::antialiasing`]. The icon must fit within the image.
+pub(crate) fn draw_identicon(
+    hash: [u8; 20],
+    config: &Config,
+    image: &mut Image<Rgba>,
+    (x, y): (u32, u32),
+) {
+    let size = (config.width, config.height);
+    if config.antialiasing == Antialiasing::None {
+        render_identicon_with(
+            hash,
+            config,
+            &mut ImageRenderer::with_offset(image, (x, y), size),
+        );
+        return;
+    }
+
+    // Anti-aliased shapes are drawn onto pixels rather than through ril
+    let (width, height) = (size.0 as usize, size.1 as usize);
+    let mut buffer = vec![0; width * height * 4];
+    antialias::render_antialiased(hash, config, &mut buffer, width * 4);
+    copy_pixels(&buffer, size.0, image, (x, y));
+}
 
-        selected_indices[i] = index;
+/// Copies tightly packed RGBA8 pixels, `width` pixels per row, onto an image at the given offset.
+/// The pixels must fit within the image.
+fn copy_pixels(buffer: &[u8], width: u32, image: &mut Image<Rgba>, (x, y): (u32, u32)) {
+    let (width, image_width) = (width as usize, image.width() as usize);
+    for (row, pixels) in buffer.chunks_exact(width * 4).enumerate() {
+        let start = (y as usize + row) * image_width + x as usize;
+        for (pixel, rgba) in image.data[start..start + width]
+            .iter_mut()
+            .zip(pixels.chunks_exact(4))
+        {
+            *pixel = Rgba::new(rgba[0], rgba[1], rgba[2], rgba[3]);
+        }
     }
+}
 
-    let [side_color, corner_color, center_color] = selected_indices;
-    let (side_color, corner_color, center_color) = (
-        color_candidates.get_from_rotation_index(side_color as usize),
-        color_candidates.get_from_rotation_index(corner_color as usize),
-        color_candidates.get_from_rotation_index(center_color as usize),
-    );
+/// Renders an identicon for the given hash, validating the configuration first. This is useful
+/// when the [`Config`] was not built with [`ConfigBuilder::build`], for example when its fields
+/// were modified directly.
+///
+/// # Returns
+/// A ril [`Image`] with the identicon rendered on it. See [`render_identicon`] for more
+/// information.
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero.
+pub fn try_render_identicon(hash: [u8; 20], config: &Config) -> Result<Image<Rgba>, RenderError> {
+    config.validate()?;
+    if config.width == 0 || config.height == 0 {
+        return Err(RenderError::ZeroSize);
+    }
 
-    let mut renderer = ShapeRenderer::new(&mut image);
-    macro_rules! render {
-        (
-            $shape_index:literal,
-            $rotation_index:expr,
-            $color:ident,
-            $render_fn:ident,
-            $render_positions:ident
-        ) => {
-            render_shape(
-                &hash,
-                $shape_index,
-                $rotation_index,
-                &mut renderer,
-                $color,
-                config.background_color,
-                offset,
-                cell,
-                $render_fn,
-                $render_positions,
-            );
-        };
+    Ok(render_identicon(hash, config))
+}
+
+/// Renders an identicon for the given hash into a caller-provided buffer of RGBA8 pixels. The hash
+/// is strictly 20-bytes long, see [`render_identicon`] for more information.
+///
+/// The icon is drawn at the start of the buffer, with rows `stride` bytes apart. To draw it at an
+/// offset, for example when tiling many icons into one sprite sheet, pass a sub-slice starting at
+/// `y * stride + x * 4`. The pixels are the same as those of [`render_identicon`] for the same hash
+/// and configuration.
+///
+/// Rendering still allocates scratch memory the size of the icon: without anti-aliasing, shapes
+/// are drawn through ril onto a temporary image, and with [`Config::antialiasing`] set, coverage
+/// or supersampled pixels are accumulated before being written to the buffer. To render many
+/// icons without allocating an image for each of them, see [`render_identicon_onto`].
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero.
+/// * [`RenderError::DestinationTooSmall`] if `stride` is less than `width * 4`, or the buffer is
+///   shorter than `stride * (height - 1) + width * 4` bytes.
+pub fn render_identicon_into(
+    hash: [u8; 20],
+    config: &Config,
+    buffer: &mut [u8],
+    stride: usize,
+) -> Result<(), RenderError> {
+    config.validate()?;
+    if config.width == 0 || config.height == 0 {
+        return Err(RenderError::ZeroSize);
     }
 
-    render!(2, Some(3), side_color, render_outer, SIDE_POSITIONS);
-    render!(4, Some(5), corner_color, render_outer, CORNER_POSITIONS);
-    render!(1, None, center_color, render_center, CENTER_POSITIONS);
+    let (width, height) = (config.width as usize, config.height as usize);
+    if stride < width * 4 || buffer.len() < stride * (height - 1) + width * 4 {
+        return Err(RenderError::DestinationTooSmall);
+    }
 
-    image
+    if config.antialiasing != Antialiasing::None {
+        antialias::render_antialiased(hash, config, buffer, stride);
+        return Ok(());
+    }
+
+    // Shapes are drawn through ril like `render_identicon`, so that every raster output has the
+    // same pixels
+    let image = render_identicon(hash, config);
+    for (row, pixels) in image.data.chunks_exact(width).enumerate() {
+        let start = row * stride;
+        for (rgba, pixel) in buffer[start..start + width * 4]
+            .chunks_exact_mut(4)
+            .zip(pixels)
+        {
+            rgba.copy_from_slice(&[pixel.r, pixel.g, pixel.b, pixel.a]);
+        }
+    }
+    Ok(())
+}
+
+/// Renders an identicon for the given hash onto an existing image, with its top-left corner at
+/// `offset`. The hash is strictly 20-bytes long, see [`render_identicon`] for more information.
+///
+/// Only the pixels covered by the icon are drawn over, so this can be used to tile many
+/// identicons into one image without allocating an image for each of them. With
+/// [`Config::antialiasing`] set, scratch memory the size of the icon is still allocated on every
+/// call to accumulate the anti-aliased pixels.
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero.
+/// * [`RenderError::DestinationTooSmall`] if the icon does not fit within the image at `offset`.
+pub fn render_identicon_onto(
+    hash: [u8; 20],
+    config: &Config,
+    image: &mut Image<Rgba>,
+    (x, y): (u32, u32),
+) -> Result<(), RenderError> {
+    config.validate()?;
+    if config.width == 0 || config.height == 0 {
+        return Err(RenderError::ZeroSize);
+    }
+
+    let fits =
+        |offset: u32, size: u32, len: u32| offset.checked_add(size).is_some_and(|end| end <= len);
+    if !fits(x, config.width, image.width()) || !fits(y, config.height, image.height()) {
+        return Err(RenderError::DestinationTooSmall);
+    }
+
+    draw_identicon(hash, config, image, (x, y));
+    Ok(())
+}
+
+/// Renders an identicon for the given hash as an SVG document. The hash is strictly 20-bytes
+/// long, see [`render_identicon`] for more information.
+///
+/// Shapes are emitted as `<path>` elements, one per color. See [`SvgRenderer`] for more
+/// information. The document has a `viewBox` of
+/// `0 0 width height` where `width` and `height` are [`Config::width`] and [`Config::height`],
+/// so it can be scaled cleanly to any size.
+///
+/// # Returns
+/// A `String` containing the SVG document.
+#[must_use]
+pub fn render_identicon_svg(hash: [u8; 20], config: &Config) -> String {
+    let mut renderer = SvgRenderer::with_dimensions(config.width, config.height);
+    render_identicon_with(hash, config, &mut renderer);
+
+    renderer.finish()
+}
+
+/// Renders an identicon for the given hash as an SVG document with both variants of a
+/// [`ThemedConfig`], which switches between them with the `prefers-color-scheme` media query. The
+/// hash is strictly 20-bytes long, see [`render_identicon`] for more information.
+///
+/// Each variant is rendered like [`render_identicon_svg`] does into its own `<g>` element, with
+/// the class `rdenticon-light` or `rdenticon-dark`. The dark variant is hidden unless the viewer
+/// prefers a dark color scheme, in which case the light variant is hidden instead. Both variants
+/// share the width, height and `viewBox` of the document, so they must have the same size.
+///
+/// # Errors
+/// * If [`ThemedConfig::validate`] fails, including when the variants have different sizes.
+///
+/// # Returns
+/// A `String` containing the SVG document.
+pub fn render_identicon_themed_svg(
+    hash: [u8; 20],
+    config: &ThemedConfig,
+) -> Result<String, ConfigBuilderError> {
+    use std::fmt::Write;
+
+    config.validate()?;
+
+    let (width, height) = (config.light.width, config.light.height);
+    let mut out = SvgRenderer::open_tag(width, height);
+    out.push_str(
+        "<style>.rdenticon-dark{display:none}@media (prefers-color-scheme:dark){\
+         .rdenticon-light{display:none}.rdenticon-dark{display:inline}}</style>",
+    );
+
+    for (class, config) in [("light", &config.light), ("dark", &config.dark)] {
+        let mut renderer = SvgRenderer::with_dimensions(width, height);
+        render_identicon_with(hash, config, &mut renderer);
+
+        let _ = write!(out, r#"<g class="rdenticon-{class}">"#);
+        renderer.write_contents(&mut out);
+        out.push_str("</g>");
+    }
+
+    out.push_str("</svg>");
+    Ok(out)
 }
 
 /// Generates an identicon for the given message. The message can be something like a username or a
-/// unique key.
+/// unique key, or any other [`IdenticonInput`] such as raw bytes, an integer ID or a UUID.
 ///
 /// # Note
 /// Identicons are hashed with SHA-1, which is not cryptographically secure. If you need a secure
-/// hash (or if you simply do not want to use SHA-1), generate a hash of 20 bytes with a separate
-/// algorithm and pass those bytes manually to [`render_identicon`].
+/// hash (or if you simply do not want to use SHA-1), use [`generate_identicon_with`] with a
+/// different [`IdenticonHasher`].
 ///
 /// # Returns
 /// A ril [`Image`] with the identicon rendered on it. See [`Image::save_inferred`] to save the
@@ -578,14 +856,46 @@ pub fn render_identicon(hash: [u8; 20], config: &Config) -> Image<Rgba> {
 ///     Ok(())
 /// }
 /// ```
-pub fn generate_identicon(message: impl AsRef<str>, config: &Config) -> Image<Rgba> {
-    let hash = sha1_smol::Sha1::from(message.as_ref()).digest().bytes();
-    render_identicon(hash, config)
+pub fn generate_identicon(message: impl IdenticonInput, config: &Config) -> Image<Rgba> {
+    generate_identicon_with::<Sha1>(message, config)
+}
+
+/// Generates an identicon for the given message, hashed with the hash algorithm `H`.
+///
+/// The digest is reduced to the 20 bytes expected by [`render_identicon`] with
+/// [`reduce_digest`], which documents the exact rule used.
+///
+/// The following hash algorithms are provided by rdenticon:
+/// * [`Sha1`], which is what [`generate_identicon`] uses.
+/// * `Sha256`, which requires the `sha256` feature.
+/// * `Blake3`, which requires the `blake3` feature.
+/// * `Md5`, which requires the `md5` feature. This is useful for Gravatar-style email hashes.
+///
+/// # Returns
+/// A ril [`Image`] with the identicon rendered on it. See [`generate_identicon`] for more
+/// information.
+///
+/// # Panics
+/// * If the digest of `H` is shorter than 6 bytes. See [`reduce_digest`] for more information.
+/// * If [`Config::width`] or [`Config::height`] is zero.
+///
+/// # Example
+/// ```no_run
+/// let config = rdenticon::Config::default();
+/// let image = rdenticon::generate_identicon_with::<rdenticon::Sha1>("username", &config);
+/// ```
+pub fn generate_identicon_with<H: IdenticonHasher>(
+    message: impl IdenticonInput,
+    config: &Config,
+) -> Image<Rgba> {
+    let digest = H::digest(&message.identicon_bytes());
+    render_identicon(reduce_digest(digest.as_ref()), config)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::borrow::Cow;
 
     #[test]
     fn test_rdenticon() -> ril::Result<()> {
@@ -600,4 +910,950 @@ mod tests {
         let image = generate_identicon("sample", &config);
         image.save_inferred("identicon.png")
     }
+
+    #[test]
+    fn test_reduce_digest() {
+        let sha1 = Sha1::digest(b"sample");
+        assert_eq!(reduce_digest(&sha1), sha1);
+
+        // MD5-sized digests keep their leading nibbles and take the hue from their last 7 nibbles
+        let md5 = *b"\x01\x23\x45\x67\x89\xab\xcd\xef\xfe\xdc\xba\x98\x76\x54\x32\x10";
+        let reduced = reduce_digest(&md5);
+        assert_eq!(reduced[..16], md5);
+        assert_eq!(reduced[16..], [0x06, 0x54, 0x32, 0x10]);
+    }
+
+    #[test]
+    fn test_identicon_input() {
+        assert_eq!(42_u64.identicon_bytes(), "42".identicon_bytes());
+        assert_eq!((-7_i32).identicon_bytes(), b"-7".identicon_bytes());
+        assert_eq!(
+            [1_u8, 2, 3].identicon_bytes(),
+            vec![1_u8, 2, 3].identicon_bytes()
+        );
+    }
+
+    #[test]
+    fn test_try_render_identicon_errors() {
+        let hash = [0; 20];
+
+        let config = Config {
+            padding: 0.75,
+            ..Config::default()
+        };
+        assert!(matches!(
+            try_render_identicon(hash, &config),
+            Err(RenderError::InvalidConfig(
+                ConfigBuilderError::InvalidPadding
+            ))
+        ));
+
+        // NaN and reversed lightness ranges are rejected
+        for color_lightness in [f64::NAN..=0.5, 0.8..=0.4] {
+            let config = Config {
+                color_lightness,
+                ..Config::default()
+            };
+            assert!(matches!(
+                try_render_identicon(hash, &config),
+                Err(RenderError::InvalidConfig(
+                    ConfigBuilderError::InvalidColorLightness
+                ))
+            ));
+        }
+
+        let config = Config {
+            height: 0,
+            ..Config::default()
+        };
+        assert!(matches!(
+            try_render_identicon(hash, &config),
+            Err(RenderError::ZeroSize)
+        ));
+    }
+
+    #[test]
+    fn test_try_render_identicon_sweep() {
+        let hashes = (0..16_u32)
+            .map(|i| Sha1::digest(&i.to_be_bytes()))
+            .chain([[0x00; 20], [0xff; 20]]);
+
+        let configs = [
+            Config::default(),
+            Config::builder().hues([0.0, 180.0, 359.9]).build().unwrap(),
+            Config::builder().jdenticon_compat(true).build().unwrap(),
+        ];
+
+        for hash in hashes {
+            for config in &configs {
+                for size in 1..=32 {
+                    for padding in [0.0, 0.08, 0.3, 0.5] {
+                        let config = Config {
+                            width: size,
+                            height: size,
+                            padding,
+                            ..config.clone()
+                        };
+
+                        let image = try_render_identicon(hash, &config).expect("failed to render");
+                        assert_eq!(image.width(), size);
+                        let _ = render_identicon_svg(hash, &config);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_rdenticon_svg() {
+        let config = Config::builder()
+            .size(64)
+            .background_color(Rgba::transparent())
+            .build()
+            .expect("invalid config");
+
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let svg = render_identicon_svg(hash, &config);
+
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="64""#));
+        assert!(svg.ends_with("</svg>"));
+        assert!(!svg.contains("<rect"));
+        assert!(svg.contains("<path fill=\"#"));
+
+        // Cutouts are holes in the path of their shape rather than background-colored paths, so
+        // they stay transparent
+        fn frame(renderer: &mut ShapeRenderer, cell: ShapeCell) {
+            let m = cell.size / 4;
+            renderer
+                .rectangle((0, 0), (cell.size, cell.size), false)
+                .rectangle((m, m), (cell.size - 2 * m, cell.size - 2 * m), true);
+        }
+        let config = Config {
+            shapes: ShapeSet {
+                outer: Cow::Borrowed(&[frame]),
+                center: Cow::Borrowed(&[frame]),
+            },
+            ..config
+        };
+        let mut colors = IdenticonSpec::new(hash, &config)
+            .groups
+            .iter()
+            .map(|group| group.color)
+            .collect::<Vec<_>>();
+        colors.dedup();
+
+        let svg = render_identicon_svg(hash, &config);
+        assert!(!svg.contains("fill-opacity"));
+        assert_eq!(svg.matches("<path").count(), colors.len());
+        assert_eq!(svg.matches('M').count(), 16 * 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_config_serde() {
+        let config = Config::builder()
+            .hues([120.0, 240.0])
+            .color_lightness(0.2..=0.6)
+            .background_color(Rgba::new(0x12, 0x34, 0x56, 0x78))
+            .build()
+            .expect("invalid config");
+
+        let json = serde_json::to_string(&config).expect("failed to serialize");
+        assert!(json.contains(r##""background_color":"#12345678""##));
+        assert!(json.contains(r#""color_lightness":[0.2,0.6]"#));
+
+        let parsed: Config = serde_json::from_str(&json).expect("failed to deserialize");
+        assert_eq!(parsed.hues, config.hues);
+        assert_eq!(parsed.color_lightness, config.color_lightness);
+        assert_eq!(parsed.background_color, config.background_color);
+
+        let parsed: Config =
+            serde_json::from_str(r##"{"width":64,"alignment":"tile","background_color":"#fff"}"##)
+                .expect("invalid");
+        assert_eq!((parsed.width, parsed.height), (64, 256));
+        assert_eq!(parsed.alignment, Alignment::Tile);
+        assert_eq!(parsed.background_color, Rgba::white());
+        assert_eq!(parsed.padding, Config::default().padding);
+
+        let parsed: Config = serde_json::from_str(r#"{"size":64,"height":32}"#).expect("invalid");
+        assert_eq!((parsed.width, parsed.height), (64, 32));
+        assert!(!serde_json::to_string(&parsed)
+            .unwrap()
+            .contains(r#""size""#));
+
+        let parsed: Config = serde_json::from_str(
+            r##"{"palettes":[{"dark_gray":"#111","mid_color":"#888","light_gray":"#eee",
+                "light_color":"#8cf","dark_color":"#048"}]}"##,
+        )
+        .expect("invalid");
+        assert_eq!(
+            parsed.palettes[0].light_color,
+            Rgba::new(0x88, 0xcc, 0xff, 0xff)
+        );
+
+        assert!(serde_json::from_str::<Config>(r#"{"padding":0.75}"#).is_err());
+        assert!(serde_json::from_str::<Config>(r##"{"background_color":"#12"}"##).is_err());
+        assert!(serde_json::from_str::<Config>(r##"{"background_color":"#+f+f+f"}"##).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_jdenticon_json() {
+        let config = Config::from_jdenticon_json(
+            r##"{
+                "hues": [207, -90],
+                "lightness": { "color": [0.4, 0.8], "grayscale": [0.3, 0.9] },
+                "saturation": { "color": 0.48, "grayscale": 0.1 },
+                "backColor": "#86444400",
+                "padding": 0.1
+            }"##,
+        )
+        .expect("invalid config");
+
+        assert_eq!(config.hues, [207.0, 270.0]);
+        assert_eq!(config.color_saturation, 0.48);
+        assert_eq!(config.background_color, Rgba::new(0x86, 0x44, 0x44, 0x00));
+        assert_eq!(config.padding, 0.1);
+
+        let parsed = Config::from_jdenticon_json(&config.to_jdenticon_json()).expect("invalid");
+        assert_eq!(parsed.hues, config.hues);
+        assert_eq!(parsed.grayscale_lightness, config.grayscale_lightness);
+        assert_eq!(parsed.grayscale_saturation, config.grayscale_saturation);
+        assert_eq!(parsed.background_color, config.background_color);
+
+        let config = Config::from_jdenticon_json(r#"{"hues": 10}"#).expect("invalid config");
+        assert_eq!(config.hues, [10.0]);
+        assert_eq!(config.background_color, Rgba::transparent());
+
+        // A single saturation only sets the color saturation
+        let config = Config::from_jdenticon_json(r#"{"saturation": 0.7}"#).expect("invalid config");
+        assert_eq!(config.color_saturation, 0.7);
+        assert_eq!(
+            config.grayscale_saturation,
+            Config::default().grayscale_saturation
+        );
+
+        assert!(matches!(
+            Config::from_jdenticon_json(r#"{"backColor": "red"}"#),
+            Err(ConfigBuilderError::MalformedColor(_)),
+        ));
+        assert!(matches!(
+            Config::from_jdenticon_json(r#"{"padding": 0.75}"#),
+            Err(ConfigBuilderError::InvalidPadding),
+        ));
+        assert!(matches!(
+            Config::from_jdenticon_json("{"),
+            Err(ConfigBuilderError::MalformedJson(_)),
+        ));
+    }
+
+    #[test]
+    fn test_render_identicon_into() {
+        let config = Config::builder()
+            .size(32)
+            .background_color(Rgba::new(1, 2, 3, 255))
+            .build()
+            .expect("invalid config");
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+
+        // Draw at (2, 1) within a 40x36 buffer, leaving the rest untouched
+        let stride = 40 * 4;
+        let mut buffer = vec![0; stride * 36];
+        render_identicon_into(hash, &config, &mut buffer[stride + 2 * 4..], stride)
+            .expect("failed to render");
+
+        assert_eq!(buffer[..stride], [0; 40 * 4]);
+        assert_eq!(buffer[stride..stride + 8], [0; 8]);
+        assert_eq!(buffer[stride + 8..stride + 12], [1, 2, 3, 255]);
+        assert_ne!(buffer[stride..stride * 33], vec![0; stride * 32]);
+
+        assert!(matches!(
+            render_identicon_into(hash, &config, &mut buffer, 32 * 4 - 1),
+            Err(RenderError::DestinationTooSmall),
+        ));
+        assert!(matches!(
+            render_identicon_into(hash, &config, &mut buffer[..stride * 31], stride),
+            Err(RenderError::DestinationTooSmall),
+        ));
+
+        let mut image = Image::new(64, 40, Rgba::transparent());
+        render_identicon_onto(hash, &config, &mut image, (32, 8)).expect("failed to render");
+        assert_eq!(image.pixel(31, 8), &Rgba::transparent());
+        assert_eq!(image.pixel(32, 8), &Rgba::new(1, 2, 3, 255));
+        assert_eq!(image.pixel(32, 7), &Rgba::transparent());
+        assert!(matches!(
+            render_identicon_onto(hash, &config, &mut image, (33, 8)),
+            Err(RenderError::DestinationTooSmall),
+        ));
+
+        // Every raster entry point has the same pixels, with and without anti-aliasing
+        for antialiasing in [
+            Antialiasing::None,
+            Antialiasing::Coverage,
+            Antialiasing::Supersample {
+                factor: 4,
+                filter: DownscaleFilter::Lanczos3,
+            },
+        ] {
+            let config = Config {
+                antialiasing,
+                ..config.clone()
+            };
+            let expected = render_identicon(hash, &config)
+                .data
+                .iter()
+                .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, pixel.a])
+                .collect::<Vec<_>>();
+
+            let mut buffer = vec![0; 32 * 32 * 4];
+            render_identicon_into(hash, &config, &mut buffer, 32 * 4).expect("failed to render");
+            assert_eq!(buffer, expected);
+
+            let mut image = Image::new(48, 48, Rgba::transparent());
+            render_identicon_onto(hash, &config, &mut image, (8, 8)).expect("failed to render");
+            let drawn = image
+                .data
+                .chunks_exact(48)
+                .skip(8)
+                .take(32)
+                .flat_map(|row| &row[8..40])
+                .flat_map(|pixel| [pixel.r, pixel.g, pixel.b, pixel.a])
+                .collect::<Vec<_>>();
+            assert_eq!(drawn, expected);
+        }
+    }
+
+    #[test]
+    fn test_non_square() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let square = render_identicon_svg(hash, &Config::builder().size(64).build().unwrap());
+        #[allow(deprecated)]
+        let size = Config::builder().size(64).build().unwrap().size();
+        assert_eq!(size, 64);
+
+        for alignment in [Alignment::Center, Alignment::Fit, Alignment::Tile] {
+            let config = Config::builder()
+                .size(64)
+                .alignment(alignment)
+                .build()
+                .unwrap();
+            assert_eq!(render_identicon_svg(hash, &config), square);
+
+            for (width, height) in [(128, 64), (64, 200), (1, 9), (33, 32)] {
+                let config = Config::builder()
+                    .width(width)
+                    .height(height)
+                    .alignment(alignment)
+                    .build()
+                    .unwrap();
+
+                let image = try_render_identicon(hash, &config).expect("failed to render");
+                assert_eq!((image.width(), image.height()), (width, height));
+                assert!(render_identicon_svg(hash, &config)
+                    .contains(&format!("viewBox=\"0 0 {width} {height}\"")));
+            }
+        }
+
+        // A 2:1 banner holds two tiles, each matching the square icon
+        let config = Config::builder()
+            .width(128)
+            .height(64)
+            .padding(0.0)
+            .background_color(Rgba::new(1, 2, 3, 255))
+            .alignment(Alignment::Tile)
+            .build()
+            .unwrap();
+        let mut banner = vec![0; 128 * 64 * 4];
+        render_identicon_into(hash, &config, &mut banner, 128 * 4).expect("failed to render");
+
+        let config = Config {
+            width: 64,
+            ..config
+        };
+        let mut tile = vec![0; 64 * 64 * 4];
+        render_identicon_into(hash, &config, &mut tile, 64 * 4).expect("failed to render");
+        for (row, tile_row) in banner.chunks_exact(128 * 4).zip(tile.chunks_exact(64 * 4)) {
+            assert_eq!(row[..64 * 4], *tile_row);
+            assert_eq!(row[64 * 4..], *tile_row);
+        }
+    }
+
+    #[test]
+    fn test_grid_size() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let default_svg = render_identicon_svg(hash, &Config::builder().size(50).build().unwrap());
+
+        for grid_size in 4..=10 {
+            let config = Config::builder()
+                .size(50)
+                .grid_size(grid_size)
+                .build()
+                .expect("invalid config");
+            let svg = render_identicon_svg(hash, &config);
+
+            assert_eq!(grid_size == 4, svg == default_svg);
+            try_render_identicon(hash, &config).expect("failed to render");
+        }
+
+        for grid_size in [0, 3, 11] {
+            assert!(matches!(
+                Config::builder().grid_size(grid_size).build(),
+                Err(ConfigBuilderError::InvalidGridSize),
+            ));
+        }
+        assert!(matches!(
+            Config::builder()
+                .grid_size(5)
+                .jdenticon_compat(true)
+                .build(),
+            Err(ConfigBuilderError::InvalidGridSize),
+        ));
+    }
+
+    #[test]
+    fn test_shape_set() {
+        fn fill(renderer: &mut ShapeRenderer, cell: ShapeCell) {
+            renderer.rectangle((0, 0), (cell.size, cell.size), false);
+        }
+        fn empty(_: &mut ShapeRenderer, _: ShapeCell) {}
+
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let config = Config::builder()
+            .size(64)
+            .padding(0.0)
+            .background_color(Rgba::new(1, 2, 3, 255))
+            .shapes(ShapeSet {
+                outer: Cow::Borrowed(&[fill]),
+                center: Cow::Borrowed(&[empty, empty]),
+            })
+            .build()
+            .expect("invalid config");
+
+        // Every outer cell is filled, while the center is left blank
+        let image = try_render_identicon(hash, &config).expect("failed to render");
+        for (x, y) in [(0, 0), (24, 8), (63, 40), (8, 63)] {
+            assert_ne!(image.pixel(x, y), &Rgba::new(1, 2, 3, 255));
+        }
+        for (x, y) in [(20, 20), (44, 44), (32, 26)] {
+            assert_eq!(image.pixel(x, y), &Rgba::new(1, 2, 3, 255));
+        }
+
+        let shapes = ShapeSet {
+            center: Cow::Borrowed(&[]),
+            ..ShapeSet::default()
+        };
+        assert!(matches!(
+            Config::builder().shapes(shapes).build(),
+            Err(ConfigBuilderError::EmptyShapeSet),
+        ));
+    }
+
+    #[test]
+    fn test_many_vertices() {
+        fn dodecagon(renderer: &mut ShapeRenderer, cell: ShapeCell) {
+            let radius = cell.size as f64 / 2.0;
+            let points = (0..12).map(|i| {
+                let angle = i as f64 / 12.0 * std::f64::consts::TAU;
+                (
+                    radius.mul_add(angle.cos(), radius) as u32,
+                    radius.mul_add(angle.sin(), radius) as u32,
+                )
+            });
+            renderer.polygon(points, false);
+        }
+
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let config = Config::builder()
+            .size(64)
+            .padding(0.0)
+            .background_color(Rgba::new(1, 2, 3, 255))
+            .shapes(ShapeSet {
+                outer: Cow::Borrowed(&[dodecagon]),
+                center: Cow::Borrowed(&[dodecagon]),
+            })
+            .build()
+            .expect("invalid config");
+
+        // The middle of every cell is filled, while the corners of cells are left blank
+        let mut buffer = vec![0; 64 * 64 * 4];
+        render_identicon_into(hash, &config, &mut buffer, 64 * 4).expect("failed to render");
+        let pixel = |x: usize, y: usize| &buffer[(y * 64 + x) * 4..(y * 64 + x + 1) * 4];
+        for (x, y) in [(8, 8), (24, 8), (40, 40), (56, 24)] {
+            assert_ne!(pixel(x, y), [1, 2, 3, 255]);
+        }
+        for (x, y) in [(0, 0), (16, 16), (63, 32)] {
+            assert_eq!(pixel(x, y), [1, 2, 3, 255]);
+        }
+
+        assert_eq!(
+            render_identicon_svg(hash, &config).matches('L').count(),
+            16 * 11
+        );
+
+        // Stretched grids draw the same polygons
+        let config = Config {
+            width: 96,
+            alignment: Alignment::Fit,
+            ..config
+        };
+        let mut buffer = vec![0; 96 * 64 * 4];
+        render_identicon_into(hash, &config, &mut buffer, 96 * 4).expect("failed to render");
+        assert_ne!(buffer[(8 * 96 + 12) * 4..(8 * 96 + 13) * 4], [1, 2, 3, 255]);
+    }
+
+    #[test]
+    fn test_palettes() {
+        let palette = |seed: u8| Palette {
+            dark_gray: Rgba::new(seed, 0, 0, 255),
+            mid_color: Rgba::new(seed, 1, 0, 255),
+            light_gray: Rgba::new(seed, 2, 0, 255),
+            light_color: Rgba::new(seed, 3, 0, 255),
+            dark_color: Rgba::new(seed, 4, 0, 255),
+        };
+        let config = Config::builder()
+            .palettes([palette(0x10), palette(0x20)])
+            .background_color(Rgba::transparent())
+            .build()
+            .expect("invalid config");
+
+        let mut seen = [false; 2];
+        for i in 0..64_u32 {
+            let svg = render_identicon_svg(Sha1::digest(&i.to_be_bytes()), &config);
+            let roles = svg
+                .match_indices("fill=\"#")
+                .map(|(start, _)| &svg[start + 7..start + 13])
+                .collect::<Vec<_>>();
+
+            // All colors come from a single palette, and never two dark or two light roles
+            let seed = &roles[0][..2];
+            seen[usize::from(seed == "20")] = true;
+            assert!(roles.iter().all(|role| role.starts_with(seed)));
+            let has = |role: u8| roles.contains(&format!("{seed}{role:02x}00").as_str());
+            assert!(!(has(0) && has(4) || has(2) && has(3)));
+        }
+        assert_eq!(seen, [true, true]);
+    }
+
+    #[test]
+    fn test_color_space() {
+        fn luminance(color: Rgba) -> f64 {
+            let linear = |c: u8| (f64::from(c) / 255.0).powf(2.2);
+            0.0722f64.mul_add(
+                linear(color.b),
+                0.2126f64.mul_add(linear(color.r), 0.7152 * linear(color.g)),
+            )
+        }
+
+        // Yellow and blue are far apart in HSL lightness, but not in perceptual color spaces
+        let spread = |color_space| {
+            let config = Config::builder()
+                .color_space(color_space)
+                .build()
+                .expect("invalid config");
+            let yellow = config.color_candidates(60.0).mid_color;
+            let blue = config.color_candidates(240.0).mid_color;
+            (luminance(yellow) - luminance(blue)).abs()
+        };
+        assert!(spread(ColorSpace::Hsl) > 0.2);
+        assert!(spread(ColorSpace::Oklch) < 0.05);
+        assert!(spread(ColorSpace::Cielch) < 0.05);
+
+        // Grayscale colors have no chroma, so they stay neutral in every color space
+        let config = Config::builder()
+            .color_space(ColorSpace::Oklch)
+            .build()
+            .expect("invalid config");
+        let gray = config.color_candidates(108.0).light_gray;
+        assert!(gray.r.abs_diff(gray.g) <= 1 && gray.g.abs_diff(gray.b) <= 1);
+    }
+
+    #[test]
+    fn test_identicon_spec() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let config = Config::builder()
+            .size(60)
+            .grid_size(6)
+            .build()
+            .expect("invalid config");
+        let spec = IdenticonSpec::new(hash, &config);
+
+        // Two rings of sides and corners, then the center
+        assert_eq!(spec.groups.len(), 5);
+        let mut cells = spec
+            .cells()
+            .map(|(_, cell)| (cell.x, cell.y))
+            .collect::<Vec<_>>();
+        cells.sort_unstable();
+        cells.dedup();
+        assert_eq!(cells.len(), 36);
+        assert!(spec.cells().all(|(_, cell)| cell.rotation < 4));
+
+        // The inner ring swaps the colors of its sides and corners
+        let palette = config.color_candidates(spec.hue);
+        let [side, corner, center] = spec.roles.map(|role| palette.get(role));
+        let colors = spec
+            .groups
+            .iter()
+            .map(|group| group.color)
+            .collect::<Vec<_>>();
+        assert_eq!(colors, [side, corner, corner, side, center]);
+
+        let mut renderer = SvgRenderer::new(60);
+        render_spec_with(&spec, &config, &mut renderer);
+        assert_eq!(renderer.finish(), render_identicon_svg(hash, &config));
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&spec).expect("failed to serialize");
+            assert!(json.contains(r#""shape":{"outer":"#));
+            let parsed: IdenticonSpec = serde_json::from_str(&json).expect("failed to deserialize");
+            assert_eq!(parsed, spec);
+        }
+    }
+
+    #[test]
+    fn test_describe() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let config = Config::builder()
+            .hues([180.0])
+            .build()
+            .expect("invalid config");
+        let description = describe(hash, &config);
+        assert!(description.starts_with("teal identicon with "));
+        assert!(description.ends_with(" center"));
+
+        let config = Config::builder()
+            .color_saturation(0.0)
+            .build()
+            .expect("invalid config");
+        assert!(describe(hash, &config).starts_with("gray identicon"));
+        // The color roles of grayscale identicons are not named after the gray roles
+        for (color_role, gray_role) in [
+            (ColorRole::LightColor, ColorRole::LightGray),
+            (ColorRole::DarkColor, ColorRole::DarkGray),
+        ] {
+            assert_ne!(
+                English.role(color_role, "gray"),
+                English.role(gray_role, "gray")
+            );
+        }
+
+        // Colors are named after the pixels, and an OKLCH hue of 270 degrees renders as blue
+        let config = Config::builder()
+            .hues([270.0])
+            .color_space(ColorSpace::Oklch)
+            .build()
+            .expect("invalid config");
+        assert!(describe(hash, &config).starts_with("blue identicon with "));
+
+        // Localized words are given the shapes and roles picked for the spec
+        struct Codes;
+        impl Words for Codes {
+            fn color(&self, hue: Option<f64>) -> String {
+                format!("{}", hue.unwrap_or(-1.0).round())
+            }
+            fn shape(&self, shape: ShapeKind) -> String {
+                format!("{shape:?}")
+            }
+            fn role(&self, role: ColorRole, _color: &str) -> String {
+                format!("{role:?}")
+            }
+            fn sentence(&self, color: &str, parts: [DescribedPart; 3]) -> String {
+                let parts = parts.map(|part| self.role(part.role, color) + &self.shape(part.shape));
+                format!("{color}: {}", parts.join(", "))
+            }
+        }
+
+        let config = Config::default();
+        let spec = IdenticonSpec::new(hash, &config);
+        let group = |index: usize| format!("{:?}", spec.groups[index].shape);
+        let description = describe_with(hash, &config, &Codes);
+        let (hue, parts) = description.split_once(": ").expect("missing color");
+        let difference = (hue.parse::<f64>().unwrap() - spec.hue).rem_euclid(360.0);
+        assert!(!(15.0..=345.0).contains(&difference));
+
+        let expected = format!(
+            "{:?}{}, {:?}{}, {:?}{}",
+            spec.roles[0],
+            group(0),
+            spec.roles[1],
+            group(1),
+            spec.roles[2],
+            group(2),
+        );
+        assert_eq!(parts, expected);
+    }
+
+    #[test]
+    fn test_min_contrast() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let pale = Rgba::new(0xf0, 0xf0, 0xf0, 255);
+        let palette = Palette {
+            dark_gray: pale,
+            mid_color: pale,
+            light_gray: pale,
+            light_color: pale,
+            dark_color: pale,
+        };
+
+        let config = Config::builder()
+            .palettes([palette])
+            .build()
+            .expect("invalid config");
+        assert!(IdenticonSpec::new(hash, &config).adjusted_roles.is_empty());
+
+        let config = Config::builder()
+            .palettes([palette])
+            .min_contrast(3.0)
+            .build()
+            .expect("invalid config");
+        let spec = IdenticonSpec::new(hash, &config);
+        let mut roles = spec.roles.to_vec();
+        roles.retain(|role| spec.adjusted_roles.contains(role));
+        assert_eq!(roles, spec.roles);
+        assert!(spec.adjusted_roles.iter().all(|role| roles.contains(role)));
+        for group in &spec.groups {
+            let ratio = contrast_ratio(group.color, Rgba::white());
+            assert!((3.0..3.1).contains(&ratio), "{ratio}");
+        }
+
+        // Light colors are lightened further on dark backgrounds
+        let config = Config::builder()
+            .background_color(Rgba::new(0x20, 0x20, 0x20, 255))
+            .min_contrast(4.5)
+            .build()
+            .expect("invalid config");
+        for i in 0..32_u32 {
+            let spec = IdenticonSpec::new(Sha1::digest(&i.to_be_bytes()), &config);
+            assert!(spec
+                .groups
+                .iter()
+                .all(|group| contrast_ratio(group.color, config.background_color) >= 4.5));
+        }
+
+        assert!(matches!(
+            Config::builder().min_contrast(0.5).build(),
+            Err(ConfigBuilderError::InvalidMinContrast),
+        ));
+    }
+
+    #[test]
+    fn test_themed_config() {
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let light = Config::builder()
+            .size(64)
+            .background_color(Rgba::new(0xf0, 0xe0, 0xd0, 255))
+            .build()
+            .expect("invalid config");
+        let themed = ThemedConfig::from_light(light.clone());
+        themed.validate().expect("invalid dark variant");
+
+        let dark = themed.get(Theme::Dark);
+        assert!(dark.mirror_lightness);
+        assert_eq!(dark.color_lightness, light.color_lightness);
+        assert_eq!(dark.background_color, Rgba::new(0x2f, 0x1f, 0x0f, 255));
+
+        // Roles keep standing out against the background, or blending into it, in both variants
+        for config in [&light, dark] {
+            let (_, palette) = spec::pick_colors(&into_nibbles(hash), config);
+            let contrast = |role| contrast_ratio(palette.get(role), config.background_color);
+
+            assert!(contrast(ColorRole::DarkGray) >= 4.5);
+            assert!(contrast(ColorRole::LightGray) < 1.5);
+            assert!(contrast(ColorRole::DarkColor) > contrast(ColorRole::MidColor));
+            assert!(contrast(ColorRole::MidColor) > contrast(ColorRole::LightColor));
+        }
+
+        // Palettes are mirrored role by role
+        let palette = Palette {
+            dark_gray: Rgba::new(0x20, 0x20, 0x20, 255),
+            mid_color: Rgba::new(0x40, 0x80, 0xc0, 255),
+            light_gray: Rgba::new(0xe0, 0xe0, 0xe0, 255),
+            light_color: Rgba::new(0xa0, 0xc0, 0xe0, 255),
+            dark_color: Rgba::new(0x10, 0x30, 0x50, 255),
+        };
+        let themed_palette = ThemedConfig::from_light(
+            Config::builder()
+                .palettes([palette])
+                .build()
+                .expect("invalid config"),
+        );
+        let (_, mirrored) = spec::pick_colors(&into_nibbles(hash), &themed_palette.dark);
+        assert_eq!(mirrored.dark_gray, Rgba::new(0xdf, 0xdf, 0xdf, 255));
+        assert_eq!(mirrored.light_gray, Rgba::new(0x1f, 0x1f, 0x1f, 255));
+        assert_eq!(mirrored.mid_color, Rgba::new(0x3f, 0x7f, 0xbf, 255));
+        assert_eq!(mirrored.dark_color, Rgba::new(0xaf, 0xcf, 0xef, 255));
+
+        // Both variants share the size of the document
+        let mut mismatched = themed.clone();
+        mismatched.dark.width = 32;
+        assert!(matches!(
+            render_identicon_themed_svg(hash, &mismatched),
+            Err(ConfigBuilderError::MismatchedThemeSize),
+        ));
+
+        // Both variants are embedded as they would be rendered on their own
+        let svg = render_identicon_themed_svg(hash, &themed).expect("failed to render");
+        assert!(svg.contains("@media (prefers-color-scheme:dark)"));
+        for (class, config) in [("light", &light), ("dark", dark)] {
+            let variant = render_identicon_svg(hash, config);
+            let contents = &variant[variant.find("\"><").unwrap() + 2..variant.len() - 6];
+            assert!(svg.contains(&format!(r#"<g class="rdenticon-{class}">{contents}</g>"#)));
+        }
+    }
+
+    #[test]
+    fn test_render_sprite_sheet() {
+        let config = Config::builder().size(16).build().expect("invalid config");
+        let options = SpriteSheetOptions {
+            columns: 3,
+            spacing: 2,
+            labels: true,
+            index_offset: 10,
+            ..SpriteSheetOptions::default()
+        };
+
+        let sheet =
+            render_sprite_sheet(["a", "b", "c", "d"], &config, &options).expect("failed to render");
+        assert_eq!(sheet.image.width(), 3 * 16 + 2 * 2);
+        assert_eq!(sheet.image.height(), 2 * (16 + 7) + 2);
+        assert_eq!(sheet.cells.len(), 4);
+
+        let cell = &sheet.cells[3];
+        assert_eq!((cell.input, cell.index), ("d", 13));
+        assert_eq!((cell.x, cell.y, cell.width, cell.height), (0, 25, 16, 16));
+        assert_eq!(sheet.image.pixel(0, 25), &config.background_color);
+        assert_eq!(sheet.image.pixel(16, 0), &Rgba::transparent());
+
+        assert!(matches!(
+            render_sprite_sheet(Vec::<&str>::new(), &config, &options),
+            Err(RenderError::ZeroSize),
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "rayon")]
+    fn test_render_batch() {
+        use std::sync::Mutex;
+
+        let config = Config::builder().size(32).build().expect("invalid config");
+        let inputs = (0..64).collect::<Vec<u32>>();
+
+        let images = render_batch(&inputs, &config).expect("failed to render");
+        assert_eq!(images.len(), inputs.len());
+        for (input, image) in inputs.iter().zip(&images) {
+            assert_eq!(image.data, generate_identicon(input, &config).data);
+        }
+
+        let streamed = Mutex::new(vec![None; inputs.len()]);
+        render_batch_streaming(&inputs, &config, |index, image| {
+            streamed.lock().unwrap()[index] = Some(image.data.clone());
+        })
+        .expect("failed to render");
+        for (streamed, image) in streamed.into_inner().unwrap().into_iter().zip(&images) {
+            assert_eq!(streamed.as_ref(), Some(&image.data));
+        }
+
+        // A single thread renders every icon into the same image
+        let buffers = Mutex::new(Vec::new());
+        rayon::ThreadPoolBuilder::new()
+            .num_threads(1)
+            .build()
+            .expect("failed to build thread pool")
+            .install(|| {
+                render_batch_streaming(&inputs, &config, |_, image| {
+                    let buffer = (image.data.as_ptr() as usize, image.data.len());
+                    buffers.lock().unwrap().push(buffer);
+                })
+            })
+            .expect("failed to render");
+        let buffers = buffers.into_inner().unwrap();
+        assert_eq!(buffers.len(), inputs.len());
+        assert!(buffers
+            .iter()
+            .all(|&buffer| buffer == (buffers[0].0, 32 * 32)));
+
+        // Invalid configurations are rejected before anything is rendered
+        let config = Config {
+            grid_size: 42,
+            ..config
+        };
+        assert!(matches!(
+            render_batch(&inputs, &config),
+            Err(RenderError::InvalidConfig(
+                ConfigBuilderError::InvalidGridSize
+            )),
+        ));
+        assert!(matches!(
+            render_batch_streaming(&inputs, &config, |_, _| unreachable!()),
+            Err(RenderError::InvalidConfig(
+                ConfigBuilderError::InvalidGridSize
+            )),
+        ));
+    }
+
+    #[test]
+    fn test_antialiasing() {
+        // Shapes that share an edge meet without a seam, and edges are blended by coverage
+        let mut buffer = vec![0; 8 * 8 * 4];
+        let mut renderer = CoverageRenderer::new(&mut buffer, 8 * 4, 8, 8);
+        renderer.background(Rgba::white());
+        renderer.begin_shape(Rgba::black());
+        renderer.polygon(&[(1.0, 1.0), (7.0, 1.0), (7.0, 7.0)], false);
+        renderer.polygon(&[(1.0, 1.0), (7.0, 7.0), (1.0, 7.0)], false);
+        renderer.rectangle((0.0, 7.5), (8.0, 0.5), false);
+        renderer.end_shape();
+
+        let pixel = |x: usize, y: usize| &buffer[(y * 8 + x) * 4..(y * 8 + x + 1) * 4];
+        assert!((1..7).all(|y| (1..7).all(|x| pixel(x, y) == [0, 0, 0, 255])));
+        assert_eq!(pixel(0, 0), [255, 255, 255, 255]);
+        assert!(pixel(3, 7)[0].abs_diff(128) <= 1);
+
+        // Every mode blends colors along edges, unlike rendering without anti-aliasing
+        let hash = sha1_smol::Sha1::from("sample").digest().bytes();
+        let colors = |antialiasing: Antialiasing| {
+            let config = Config::builder()
+                .size(32)
+                .antialiasing(antialiasing)
+                .build()
+                .expect("invalid config");
+            let mut buffer = vec![0; 32 * 32 * 4];
+            render_identicon_into(hash, &config, &mut buffer, 32 * 4).expect("failed to render");
+
+            let mut colors = buffer.chunks_exact(4).collect::<Vec<_>>();
+            colors.sort_unstable();
+            colors.dedup();
+            colors.len()
+        };
+        let aliased = colors(Antialiasing::None);
+        assert!(colors(Antialiasing::Coverage) > aliased);
+        for filter in [DownscaleFilter::Box, DownscaleFilter::Lanczos3] {
+            assert!(colors(Antialiasing::Supersample { factor: 4, filter }) > aliased);
+        }
+
+        let config = Config::builder().antialiasing(Antialiasing::Supersample {
+            factor: 0,
+            filter: DownscaleFilter::Box,
+        });
+        assert!(matches!(
+            config.build(),
+            Err(ConfigBuilderError::InvalidSupersampling),
+        ));
+
+        // A supersampled size that overflows is rejected rather than panicking
+        let mut config = Config::builder()
+            .size(u32::MAX / 8)
+            .build()
+            .expect("invalid config");
+        config.antialiasing = Antialiasing::Supersample {
+            factor: 16,
+            filter: DownscaleFilter::Box,
+        };
+        assert!(matches!(
+            try_render_identicon([0; 20], &config),
+            Err(RenderError::InvalidConfig(
+                ConfigBuilderError::InvalidSupersampling
+            )),
+        ));
+    }
 }
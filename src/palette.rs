@@ -0,0 +1,100 @@
+use crate::hsl::mirror_hsl_lightness;
+use ril::Rgba;
+
+/// The colors of an identicon, one for each role a group of shapes can take.
+///
+/// By default, palettes are derived from a hue picked by the hash. Set [`Config::palettes`] to
+/// use fixed colors instead, for example those of a design system. The sides, corners and center
+/// of the identicon each pick one of these roles, and never pick both dark roles or both light
+/// roles, so shapes stay distinguishable from each other.
+///
+/// With the `serde` feature enabled, colors are represented as `#rrggbbaa` hex strings.
+///
+/// [`Config::palettes`]: crate::Config::palettes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Palette {
+    /// A dark grayscale color.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub dark_gray: Rgba,
+    /// A color of medium lightness.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub mid_color: Rgba,
+    /// A light grayscale color.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub light_gray: Rgba,
+    /// A light color.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub light_color: Rgba,
+    /// A dark color.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub dark_color: Rgba,
+}
+
+impl Palette {
+    /// Returns the color of the given role.
+    #[inline]
+    #[must_use]
+    pub const fn get(&self, role: ColorRole) -> Rgba {
+        match role {
+            ColorRole::DarkGray => self.dark_gray,
+            ColorRole::MidColor => self.mid_color,
+            ColorRole::LightGray => self.light_gray,
+            ColorRole::LightColor => self.light_color,
+            ColorRole::DarkColor => self.dark_color,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self, role: ColorRole) -> &mut Rgba {
+        match role {
+            ColorRole::DarkGray => &mut self.dark_gray,
+            ColorRole::MidColor => &mut self.mid_color,
+            ColorRole::LightGray => &mut self.light_gray,
+            ColorRole::LightColor => &mut self.light_color,
+            ColorRole::DarkColor => &mut self.dark_color,
+        }
+    }
+
+    /// Mirrors the HSL lightness of every color of this palette, keeping their hue and saturation.
+    pub(crate) fn mirror_lightness(self) -> Self {
+        Self {
+            dark_gray: mirror_hsl_lightness(self.dark_gray),
+            mid_color: mirror_hsl_lightness(self.mid_color),
+            light_gray: mirror_hsl_lightness(self.light_gray),
+            light_color: mirror_hsl_lightness(self.light_color),
+            dark_color: mirror_hsl_lightness(self.dark_color),
+        }
+    }
+}
+
+/// A role that a group of shapes takes within a [`Palette`], which determines its color.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ColorRole {
+    /// See [`Palette::dark_gray`].
+    DarkGray,
+    /// See [`Palette::mid_color`].
+    MidColor,
+    /// See [`Palette::light_gray`].
+    LightGray,
+    /// See [`Palette::light_color`].
+    LightColor,
+    /// See [`Palette::dark_color`].
+    DarkColor,
+}
+
+impl ColorRole {
+    /// Returns the role picked by a digit of the hash in the range `0..5`.
+    #[inline]
+    pub(crate) const fn from_index(index: u8) -> Self {
+        match index {
+            0 => Self::DarkGray,
+            1 => Self::MidColor,
+            2 => Self::LightGray,
+            3 => Self::LightColor,
+            _ /* 4 */ => Self::DarkColor,
+        }
+    }
+}
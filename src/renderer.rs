@@ -0,0 +1,385 @@
+use ril::prelude::*;
+
+/// A backend that identicons are drawn onto.
+///
+/// [`render_identicon_with`](crate::render_identicon_with) drives a renderer in the following
+/// order: [`background`](Self::background) is called once, then for each group of shapes (sides,
+/// corners and center), [`begin_shape`](Self::begin_shape) is called with the color of the group,
+/// followed by any number of [`polygon`](Self::polygon), [`circle`](Self::circle),
+/// [`ellipse`](Self::ellipse) and [`rectangle`](Self::rectangle) calls, and finally [`end_shape`](Self::end_shape).
+///
+/// All coordinates are absolute, in pixels, relative to the top-left corner of the icon.
+///
+/// Some shapes cut holes into other shapes of the same group. These are drawn with `invert` set
+/// to `true`, and inverted polygons have their vertices in reverse order. Raster backends should
+/// fill inverted shapes with the background color, while vector backends can rely on the reversed
+/// winding to cut the hole with a nonzero fill rule.
+///
+/// Shapes are only ever drawn between [`Renderer::begin_shape`] and [`Renderer::end_shape`], so
+/// renderers may assume a group has begun when a shape is drawn, and may panic otherwise.
+pub trait Renderer {
+    /// Fills the whole icon with the given background color. By default, this does nothing.
+    fn background(&mut self, color: Rgba) {
+        let _ = color;
+    }
+
+    /// Begins a new group of shapes filled with the given color.
+    fn begin_shape(&mut self, color: Rgba);
+
+    /// Ends the current group of shapes. By default, this does nothing.
+    fn end_shape(&mut self) {}
+
+    /// Fills a polygon with the given vertices.
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool);
+
+    /// Fills a circle whose bounding box starts at `top_left`.
+    fn circle(&mut self, top_left: (f64, f64), diameter: f64, invert: bool);
+
+    /// Fills an axis-aligned ellipse whose bounding box starts at `top_left`. This is only used
+    /// for non-square icons with [`Alignment::Fit`](crate::Alignment::Fit).
+    ///
+    /// By default, this is drawn as a circle if `width` and `height` are equal, and as a polygon
+    /// approximating the ellipse otherwise.
+    fn ellipse(&mut self, (x, y): (f64, f64), (width, height): (f64, f64), invert: bool) {
+        #[allow(clippy::float_cmp)]
+        if width == height {
+            self.circle((x, y), width, invert);
+            return;
+        }
+
+        let mut points = ellipse_points((x, y), (width, height));
+        if invert {
+            points.reverse();
+        }
+        self.polygon(&points, invert);
+    }
+
+    /// Fills an axis-aligned rectangle. By default, this is drawn as a polygon.
+    fn rectangle(&mut self, (x, y): (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let points = [(x, y), (x + w, y), (x + w, y + h), (x, y + h)];
+        if invert {
+            self.polygon(&[points[3], points[2], points[1], points[0]], true);
+        } else {
+            self.polygon(&points, false);
+        }
+    }
+}
+
+/// The amount of vertices used to approximate ellipses by [`Renderer::ellipse`].
+const ELLIPSE_SEGMENTS: usize = 64;
+
+/// Returns the vertices of a polygon approximating the ellipse whose bounding box starts at
+/// `top_left`, in clockwise order.
+pub(crate) fn ellipse_points(
+    (x, y): (f64, f64),
+    (width, height): (f64, f64),
+) -> [(f64, f64); ELLIPSE_SEGMENTS] {
+    let (rx, ry) = (width / 2.0, height / 2.0);
+    let mut points = [(0.0, 0.0); ELLIPSE_SEGMENTS];
+    for (i, point) in points.iter_mut().enumerate() {
+        let angle = i as f64 / ELLIPSE_SEGMENTS as f64 * std::f64::consts::TAU;
+        *point = (x + rx + rx * angle.cos(), y + ry + ry * angle.sin());
+    }
+
+    points
+}
+
+/// A [`Renderer`] that draws onto a ril [`Image`].
+pub struct ImageRenderer<'a> {
+    image: &'a mut Image<Rgba>,
+    offset: (u32, u32),
+    size: Option<(u32, u32)>,
+    color: Rgba,
+    background_color: Rgba,
+}
+
+impl<'a> ImageRenderer<'a> {
+    /// Creates a new renderer that draws onto the given image.
+    pub fn new(image: &'a mut Image<Rgba>) -> Self {
+        Self {
+            image,
+            offset: (0, 0),
+            size: None,
+            color: Rgba::transparent(),
+            background_color: Rgba::transparent(),
+        }
+    }
+
+    /// Creates a new renderer that draws an icon of the given width and height onto the given
+    /// image, with its top-left corner at `offset`. The background is only filled within the icon,
+    /// which allows many icons to be drawn onto the same image.
+    pub fn with_offset(image: &'a mut Image<Rgba>, offset: (u32, u32), size: (u32, u32)) -> Self {
+        Self {
+            offset,
+            size: Some(size),
+            ..Self::new(image)
+        }
+    }
+
+    const fn fill(&self, invert: bool) -> Rgba {
+        if invert {
+            self.background_color
+        } else {
+            self.color
+        }
+    }
+
+    fn translate(&self, (x, y): (f64, f64)) -> (u32, u32) {
+        (x as u32 + self.offset.0, y as u32 + self.offset.1)
+    }
+}
+
+impl Renderer for ImageRenderer<'_> {
+    fn background(&mut self, color: Rgba) {
+        self.background_color = color;
+
+        let Some((icon_width, icon_height)) = self.size else {
+            self.image.data.fill(color);
+            return;
+        };
+        let width = self.image.width();
+        let (x, y) = self.offset;
+        let x_end = (x + icon_width).min(width);
+        let y_end = (y + icon_height).min(self.image.height());
+
+        for row in y..y_end {
+            let start = (row * width) as usize;
+            self.image.data[start + x as usize..start + x_end as usize].fill(color);
+        }
+    }
+
+    fn begin_shape(&mut self, color: Rgba) {
+        self.color = color;
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool) {
+        let polygon = Polygon::from_vertices(points.iter().map(|&pos| self.translate(pos)))
+            .with_fill(self.fill(invert));
+
+        self.image.draw(&polygon);
+    }
+
+    fn circle(&mut self, top_left: (f64, f64), diameter: f64, invert: bool) {
+        let (x, y) = self.translate(top_left);
+        let diameter = diameter as u32;
+        let circle = Ellipse::from_bounding_box(x, y, x + diameter, y + diameter)
+            .with_fill(self.fill(invert));
+
+        self.image.draw(&circle);
+    }
+
+    fn ellipse(&mut self, top_left: (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let (x, y) = self.translate(top_left);
+        let ellipse = Ellipse::from_bounding_box(x, y, x + w as u32, y + h as u32)
+            .with_fill(self.fill(invert));
+
+        self.image.draw(&ellipse);
+    }
+
+    fn rectangle(&mut self, top_left: (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let (x, y) = self.translate(top_left);
+        // ril's rectangles are one row short, which would leave gaps between adjacent cells
+        let rect = Rectangle::new()
+            .with_position(x, y)
+            .with_size(w as u32, h as u32 + 1)
+            .with_fill(self.fill(invert));
+
+        self.image.draw(&rect);
+    }
+}
+
+/// A [`Renderer`] that draws onto a caller-provided buffer of RGBA8 pixels, without allocating.
+///
+/// Rows are `stride` bytes apart, and each pixel is 4 bytes in RGBA order. Shapes are rasterized
+/// by sampling the center of each pixel and are written without blending, so their edges may
+/// differ slightly from those drawn by [`ImageRenderer`].
+pub struct BufferRenderer<'a> {
+    buffer: &'a mut [u8],
+    stride: usize,
+    width: usize,
+    height: usize,
+    color: Rgba,
+    background_color: Rgba,
+}
+
+impl<'a> BufferRenderer<'a> {
+    /// Creates a new renderer that draws onto the `width` by `height` pixels at the start of the
+    /// given buffer.
+    ///
+    /// # Panics
+    /// * If `stride` is less than `width * 4`.
+    /// * If the buffer is shorter than `stride * (height - 1) + width * 4` bytes.
+    pub fn new(buffer: &'a mut [u8], stride: usize, width: u32, height: u32) -> Self {
+        let (width, height) = (width as usize, height as usize);
+        assert!(stride >= width * 4, "stride must be at least width * 4");
+        assert!(
+            height == 0 || buffer.len() >= stride * (height - 1) + width * 4,
+            "buffer is too small",
+        );
+
+        Self {
+            buffer,
+            stride,
+            width,
+            height,
+            color: Rgba::transparent(),
+            background_color: Rgba::transparent(),
+        }
+    }
+
+    const fn fill(&self, invert: bool) -> Rgba {
+        if invert {
+            self.background_color
+        } else {
+            self.color
+        }
+    }
+
+    /// Fills the pixels of the given row whose centers are within `[x_start, x_end)`.
+    fn fill_span(&mut self, y: usize, x_start: f64, x_end: f64, color: Rgba) {
+        let end = ((x_end - 0.5).ceil().max(0.0) as usize).min(self.width);
+        let start = ((x_start - 0.5).ceil().max(0.0) as usize).min(end);
+
+        let row = y * self.stride;
+        for pixel in self.buffer[row + start * 4..row + end * 4].chunks_exact_mut(4) {
+            pixel.copy_from_slice(&[color.r, color.g, color.b, color.a]);
+        }
+    }
+
+    /// Returns the rows whose centers are within `[y_start, y_end)`.
+    fn rows(&self, y_start: f64, y_end: f64) -> std::ops::Range<usize> {
+        let end = ((y_end - 0.5).ceil().max(0.0) as usize).min(self.height);
+        let start = ((y_start - 0.5).ceil().max(0.0) as usize).min(end);
+
+        start..end
+    }
+}
+
+impl Renderer for BufferRenderer<'_> {
+    fn background(&mut self, color: Rgba) {
+        self.background_color = color;
+
+        for y in 0..self.height {
+            self.fill_span(y, 0.0, self.width as f64, color);
+        }
+    }
+
+    fn begin_shape(&mut self, color: Rgba) {
+        self.color = color;
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool) {
+        let color = self.fill(invert);
+        let (y_min, y_max) = points
+            .iter()
+            .fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), &(_, y)| {
+                (min.min(y), max.max(y))
+            });
+
+        // Even-odd scanline fill, reusing the crossings of each row for the next one
+        let mut crossings = Vec::new();
+        for y in self.rows(y_min, y_max) {
+            let center = y as f64 + 0.5;
+
+            crossings.clear();
+            for (i, &(x0, y0)) in points.iter().enumerate() {
+                let (x1, y1) = points[(i + 1) % points.len()];
+                if (y0 <= center) != (y1 <= center) {
+                    crossings.push(x0 + (center - y0) / (y1 - y0) * (x1 - x0));
+                }
+            }
+
+            crossings.sort_unstable_by(f64::total_cmp);
+            for span in crossings.chunks_exact(2) {
+                self.fill_span(y, span[0], span[1], color);
+            }
+        }
+    }
+
+    fn circle(&mut self, (x, y): (f64, f64), diameter: f64, invert: bool) {
+        let color = self.fill(invert);
+        let radius = diameter / 2.0;
+        let (cx, cy) = (x + radius, y + radius);
+
+        for row in self.rows(y, y + diameter) {
+            let dy = row as f64 + 0.5 - cy;
+            let dx = (radius * radius - dy * dy).max(0.0).sqrt();
+            self.fill_span(row, cx - dx, cx + dx, color);
+        }
+    }
+
+    fn ellipse(&mut self, (x, y): (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let color = self.fill(invert);
+        let (rx, ry) = (w / 2.0, h / 2.0);
+        let (cx, cy) = (x + rx, y + ry);
+
+        for row in self.rows(y, y + h) {
+            let dy = (row as f64 + 0.5 - cy) / ry;
+            let dx = rx * (1.0 - dy * dy).max(0.0).sqrt();
+            self.fill_span(row, cx - dx, cx + dx, color);
+        }
+    }
+
+    fn rectangle(&mut self, (x, y): (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let color = self.fill(invert);
+
+        for row in self.rows(y, y + h) {
+            self.fill_span(row, x, x + w, color);
+        }
+    }
+}
+
+/// A [`Renderer`] that scales the coordinates of another renderer along each axis, which is used
+/// to stretch the grid of [`Alignment::Fit`](crate::Alignment::Fit) icons.
+pub(crate) struct StretchRenderer<'a> {
+    pub renderer: &'a mut dyn Renderer,
+    pub origin: (f64, f64),
+    pub scale: (f64, f64),
+}
+
+impl StretchRenderer<'_> {
+    fn map(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            self.scale.0.mul_add(x, self.origin.0),
+            self.scale.1.mul_add(y, self.origin.1),
+        )
+    }
+}
+
+impl Renderer for StretchRenderer<'_> {
+    fn background(&mut self, color: Rgba) {
+        self.renderer.background(color);
+    }
+
+    fn begin_shape(&mut self, color: Rgba) {
+        self.renderer.begin_shape(color);
+    }
+
+    fn end_shape(&mut self) {
+        self.renderer.end_shape();
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], invert: bool) {
+        let points = points
+            .iter()
+            .map(|&point| self.map(point))
+            .collect::<Vec<_>>();
+        self.renderer.polygon(&points, invert);
+    }
+
+    fn circle(&mut self, top_left: (f64, f64), diameter: f64, invert: bool) {
+        self.ellipse(top_left, (diameter, diameter), invert);
+    }
+
+    fn ellipse(&mut self, top_left: (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let top_left = self.map(top_left);
+        self.renderer
+            .ellipse(top_left, (w * self.scale.0, h * self.scale.1), invert);
+    }
+
+    fn rectangle(&mut self, top_left: (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let top_left = self.map(top_left);
+        self.renderer
+            .rectangle(top_left, (w * self.scale.0, h * self.scale.1), invert);
+    }
+}
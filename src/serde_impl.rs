@@ -0,0 +1,136 @@
+use crate::{Alignment, Antialiasing, ColorSpace, Config, Palette, Rgba, ShapeSet};
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+/// The serialized representation of a [`Config`].
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+struct ConfigRepr {
+    hues: Vec<f64>,
+    color_lightness: [f64; 2],
+    grayscale_lightness: [f64; 2],
+    color_saturation: f64,
+    grayscale_saturation: f64,
+    color_space: ColorSpace,
+    palettes: Vec<Palette>,
+    #[serde(with = "hex_color")]
+    background_color: Rgba,
+    min_contrast: Option<f64>,
+    mirror_lightness: bool,
+    padding: f64,
+    width: Option<u32>,
+    height: Option<u32>,
+    /// Sets both the width and height when either of them is missing, for configurations
+    /// serialized before non-square icons were supported.
+    #[serde(skip_serializing)]
+    size: Option<u32>,
+    alignment: Alignment,
+    antialiasing: Antialiasing,
+    grid_size: u32,
+    jdenticon_compat: bool,
+}
+
+impl Default for ConfigRepr {
+    fn default() -> Self {
+        // The width and height are left out, so that `size` can take their place
+        let mut repr = Self::from(Config::default());
+        repr.width = None;
+        repr.height = None;
+        repr
+    }
+}
+
+impl From<Config> for ConfigRepr {
+    fn from(config: Config) -> Self {
+        Self {
+            hues: config.hues,
+            color_lightness: [
+                *config.color_lightness.start(),
+                *config.color_lightness.end(),
+            ],
+            grayscale_lightness: [
+                *config.grayscale_lightness.start(),
+                *config.grayscale_lightness.end(),
+            ],
+            color_saturation: config.color_saturation,
+            grayscale_saturation: config.grayscale_saturation,
+            color_space: config.color_space,
+            palettes: config.palettes,
+            background_color: config.background_color,
+            min_contrast: config.min_contrast,
+            mirror_lightness: config.mirror_lightness,
+            padding: config.padding,
+            width: Some(config.width),
+            height: Some(config.height),
+            size: None,
+            alignment: config.alignment,
+            antialiasing: config.antialiasing,
+            grid_size: config.grid_size,
+            jdenticon_compat: config.jdenticon_compat,
+        }
+    }
+}
+
+impl From<ConfigRepr> for Config {
+    fn from(repr: ConfigRepr) -> Self {
+        let [color_start, color_end] = repr.color_lightness;
+        let [grayscale_start, grayscale_end] = repr.grayscale_lightness;
+        let default = Config::default();
+
+        Self {
+            hues: repr.hues,
+            color_lightness: color_start..=color_end,
+            grayscale_lightness: grayscale_start..=grayscale_end,
+            color_saturation: repr.color_saturation,
+            grayscale_saturation: repr.grayscale_saturation,
+            color_space: repr.color_space,
+            palettes: repr.palettes,
+            background_color: repr.background_color,
+            min_contrast: repr.min_contrast,
+            mirror_lightness: repr.mirror_lightness,
+            padding: repr.padding,
+            width: repr.width.or(repr.size).unwrap_or(default.width),
+            height: repr.height.or(repr.size).unwrap_or(default.height),
+            alignment: repr.alignment,
+            antialiasing: repr.antialiasing,
+            grid_size: repr.grid_size,
+            shapes: ShapeSet::default(),
+            jdenticon_compat: repr.jdenticon_compat,
+        }
+    }
+}
+
+/// Serializes colors as `#rrggbbaa` hex strings.
+pub(crate) mod hex_color {
+    use crate::{
+        config::{format_hex_color, parse_hex_color},
+        ConfigBuilderError, Rgba,
+    };
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(color: &Rgba, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_hex_color(*color))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Rgba, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        parse_hex_color(&s).ok_or_else(|| D::Error::custom(ConfigBuilderError::MalformedColor(s)))
+    }
+}
+
+impl Serialize for Config {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ConfigRepr::from(self.clone()).serialize(serializer)
+    }
+}
+
+/// Deserializes a [`Config`], validating it with the same checks as [`ConfigBuilder::build`].
+///
+/// [`ConfigBuilder::build`]: crate::ConfigBuilder::build
+impl<'de> Deserialize<'de> for Config {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let config = Self::from(ConfigRepr::deserialize(deserializer)?);
+        config.validate().map_err(D::Error::custom)?;
+
+        Ok(config)
+    }
+}
@@ -0,0 +1,84 @@
+use crate::{render_center, render_outer, ShapeRenderer};
+use std::borrow::Cow;
+
+/// A cell of the grid that a shape is drawn into. See [`ShapeSet`] for more information.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ShapeCell {
+    /// The width and height of the cell in pixels.
+    pub size: u32,
+    /// The index of the cell within its group, for example `0` to `3` for the corners of a ring,
+    /// clockwise from the top-left corner.
+    pub index: usize,
+    /// The amount of cells in the group. The center of a grid with an odd size is a single cell,
+    /// while it is made of 4 cells otherwise.
+    pub count: usize,
+}
+
+/// A function that draws a shape into a cell of the grid.
+///
+/// Coordinates passed to the [`ShapeRenderer`] are relative to the top-left corner of the cell,
+/// and shapes are rotated for each cell of their group automatically.
+pub type ShapeFn = fn(&mut ShapeRenderer, ShapeCell);
+
+/// Wraps calls to a shape function taking a shape index into one [`ShapeFn`] per index.
+macro_rules! shapes {
+    ($render:ident($cell:ident) => [$($index:literal),*]) => {
+        [$(|renderer: &mut ShapeRenderer, $cell: ShapeCell| $render(renderer, $cell, $index)),*]
+    };
+}
+
+/// The shapes that outer cells, i.e. the corners and sides of the grid, and center cells are
+/// drawn with.
+///
+/// Each group of cells picks one shape from its list using a digit of the hash, as
+/// `digit % shapes.len()`. Digits are within the range `0..16`, so shapes past the 16th are never
+/// picked. Custom shapes are drawn with the same layout, rotations and colors as the built-in
+/// ones:
+///
+/// ```
+/// use rdenticon::{Config, ShapeCell, ShapeRenderer, ShapeSet};
+///
+/// fn square(renderer: &mut ShapeRenderer, cell: ShapeCell) {
+///     let m = cell.size / 8;
+///     renderer.rectangle((m, m), (cell.size - 2 * m, cell.size - 2 * m), false);
+/// }
+///
+/// let mut outer = ShapeSet::DEFAULT_OUTER.to_vec();
+/// outer.push(square);
+///
+/// let config = Config::builder()
+///     .shapes(ShapeSet {
+///         outer: outer.into(),
+///         ..ShapeSet::default()
+///     })
+///     .build()
+///     .expect("invalid config");
+/// ```
+///
+/// Shape sets are ignored when [`Config::jdenticon_compat`](crate::Config::jdenticon_compat) is
+/// set, and are not serialized with the `serde` feature.
+#[derive(Clone, Debug)]
+pub struct ShapeSet {
+    /// The shapes of corner and side cells. Defaults to [`ShapeSet::DEFAULT_OUTER`].
+    pub outer: Cow<'static, [ShapeFn]>,
+    /// The shapes of center cells. Defaults to [`ShapeSet::DEFAULT_CENTER`].
+    pub center: Cow<'static, [ShapeFn]>,
+}
+
+impl ShapeSet {
+    /// The 4 built-in shapes of outer cells, the same as jdenticon's.
+    pub const DEFAULT_OUTER: [ShapeFn; 4] = shapes!(render_outer(cell) => [0, 1, 2, 3]);
+
+    /// The 14 built-in shapes of center cells, the same as jdenticon's.
+    pub const DEFAULT_CENTER: [ShapeFn; 14] =
+        shapes!(render_center(cell) => [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13]);
+}
+
+impl Default for ShapeSet {
+    fn default() -> Self {
+        Self {
+            outer: Cow::Borrowed(&Self::DEFAULT_OUTER),
+            center: Cow::Borrowed(&Self::DEFAULT_CENTER),
+        }
+    }
+}
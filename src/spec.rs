@@ -0,0 +1,250 @@
+use crate::{
+    contrast::ensure_contrast, hash_substring_u32, into_nibbles, layout, ColorRole, Config,
+    Palette, Rgba, ShapeSet,
+};
+
+/// The shape of a [`ShapeGroup`], as an index into one of the lists of a [`ShapeSet`].
+///
+/// With the default shape set and with [`Config::jdenticon_compat`], these are the indices of
+/// jdenticon's built-in shapes.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ShapeKind {
+    /// An index into [`ShapeSet::outer`].
+    Outer(u8),
+    /// An index into [`ShapeSet::center`].
+    Center(u8),
+}
+
+/// A cell of the grid within a [`ShapeGroup`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CellSpec {
+    /// The column of the cell, counting from the left edge of the grid.
+    pub x: u32,
+    /// The row of the cell, counting from the top edge of the grid.
+    pub y: u32,
+    /// The amount of clockwise quarter turns the shape is rotated by, within the range `0..4`.
+    pub rotation: u8,
+}
+
+/// A group of cells that are drawn with the same shape and color, for example the corners of a
+/// ring of the grid.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShapeGroup {
+    /// The shape that each cell of the group is drawn with.
+    pub shape: ShapeKind,
+    /// The color of the shapes.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_impl::hex_color"))]
+    pub color: Rgba,
+    /// The cells of the group, in the order passed to shapes as [`ShapeCell::index`].
+    ///
+    /// [`ShapeCell::index`]: crate::ShapeCell::index
+    pub cells: Vec<CellSpec>,
+}
+
+/// Everything the hash decides about an identicon, without any drawing: its hue, its colors and
+/// the shape of each cell of its grid.
+///
+/// Identicons are rendered by building a spec with [`IdenticonSpec::new`], then drawing it with
+/// [`render_spec_with`]. Specs are independent of the size and alignment of the icon, so they can
+/// be cached, compared, or sent to clients that draw them natively. With the `serde` feature
+/// enabled, they implement `Serialize` and `Deserialize`.
+///
+/// ```
+/// use rdenticon::{render_spec_with, Config, IdenticonSpec, Sha1, IdenticonHasher, SvgRenderer};
+///
+/// let config = Config::default();
+/// let spec = IdenticonSpec::new(Sha1::digest(b"rdenticon"), &config);
+/// assert_eq!(spec.cells().count(), 16);
+///
+/// let mut renderer = SvgRenderer::new(config.width);
+/// render_spec_with(&spec, &config, &mut renderer);
+/// let svg = renderer.finish();
+/// ```
+///
+/// [`render_spec_with`]: crate::render_spec_with
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IdenticonSpec {
+    /// The hue picked by the hash in degrees, within the range `[0.0, 360.0]`. This is one of
+    /// [`Config::hues`] if any are configured.
+    ///
+    /// When [`Config::palettes`] are configured, this is the hue that picked the palette instead.
+    pub hue: f64,
+    /// The color roles of the sides, corners and center of the grid, in that order. Inner rings
+    /// swap the roles of their sides and corners with each other.
+    pub roles: [ColorRole; 3],
+    /// The color roles whose colors were lightened or darkened to reach
+    /// [`Config::min_contrast`] against the background color. This is empty unless a minimum
+    /// contrast is configured.
+    pub adjusted_roles: Vec<ColorRole>,
+    /// The amount of cells along each side of the grid.
+    pub grid_size: u32,
+    /// The groups of cells of the grid, in the order they are drawn. Rings are drawn from the
+    /// outside in, sides before corners, followed by the center.
+    pub groups: Vec<ShapeGroup>,
+}
+
+impl IdenticonSpec {
+    /// Builds the spec of the identicon for the given hash. The hash is strictly 20-bytes long,
+    /// see [`render_identicon`](crate::render_identicon) for more information.
+    #[must_use]
+    pub fn new(hash: [u8; 20], config: &Config) -> Self {
+        let hash = into_nibbles(hash);
+        let (hue, mut palette) = pick_colors(&hash, config);
+
+        let roles = select_roles(&hash);
+        let mut adjusted_roles = Vec::new();
+        if let Some(ratio) = config.min_contrast {
+            for role in roles {
+                let color = palette.get_mut(role);
+                let adjusted = ensure_contrast(*color, config.background_color, ratio);
+                if adjusted != *color && !adjusted_roles.contains(&role) {
+                    *color = adjusted;
+                    adjusted_roles.push(role);
+                }
+            }
+        }
+
+        let (outer_len, center_len) = if config.jdenticon_compat {
+            (
+                ShapeSet::DEFAULT_OUTER.len(),
+                ShapeSet::DEFAULT_CENTER.len(),
+            )
+        } else {
+            (config.shapes.outer.len(), config.shapes.center.len())
+        };
+
+        Self {
+            hue,
+            roles,
+            adjusted_roles,
+            grid_size: config.grid_size,
+            groups: build_groups(
+                &hash,
+                config.grid_size,
+                &palette,
+                roles,
+                outer_len,
+                center_len,
+            ),
+        }
+    }
+
+    /// Returns every cell of the grid along with the group it belongs to.
+    pub fn cells(&self) -> impl Iterator<Item = (&ShapeGroup, &CellSpec)> {
+        self.groups
+            .iter()
+            .flat_map(|group| group.cells.iter().map(move |cell| (group, cell)))
+    }
+}
+
+/// Picks the hue of an identicon in degrees and the colors of its roles, before they are adjusted
+/// to reach [`Config::min_contrast`].
+pub(crate) fn pick_colors(hash: &[u8; 40], config: &Config) -> (f64, Palette) {
+    let hue = hash_substring_u32::<7>(hash, 33) as f64 / 0xfffffff as f64;
+    if !config.palettes.is_empty() {
+        (hue * 360.0, config.resolve_palette(hue))
+    } else if config.jdenticon_compat {
+        let palette = config.jdenticon_color_candidates(hue);
+        (config.jdenticon_resolve_hue(hue) * 360.0, palette)
+    } else {
+        let hue = hue * 360.0;
+        (config.resolve_hue(hue), config.color_candidates(hue))
+    }
+}
+
+/// Picks the color roles of the sides, corners and center, never picking both dark roles or both
+/// light roles.
+fn select_roles(hash: &[u8; 40]) -> [ColorRole; 3] {
+    let mut selected_indices = [!0; 3];
+    // `.contains` optimization
+    macro_rules! contains_opt {
+        ($value:literal) => {{
+            selected_indices[0] == $value
+                || selected_indices[1] == $value
+                || selected_indices[2] == $value
+        }};
+    }
+
+    for i in 0..3 {
+        let index = hash[i + 8] % 5;
+        let index = match index {
+            0 | 4 if contains_opt!(0) || contains_opt!(4) => 1,
+            2 | 3 if contains_opt!(2) || contains_opt!(3) => 1,
+            _ => index,
+        };
+
+        selected_indices[i] = index;
+    }
+
+    selected_indices.map(ColorRole::from_index)
+}
+
+fn build_groups(
+    hash: &[u8; 40],
+    grid_size: u32,
+    palette: &Palette,
+    [side_role, corner_role, center_role]: [ColorRole; 3],
+    outer_len: usize,
+    center_len: usize,
+) -> Vec<ShapeGroup> {
+    let group = |shape: ShapeKind,
+                 rotation_index: Option<usize>,
+                 role: ColorRole,
+                 cells: &mut dyn Iterator<Item = layout::Cell>| {
+        let rotation = rotation_index.map(|idx| hash[idx]).unwrap_or_default();
+        ShapeGroup {
+            shape,
+            color: palette.get(role),
+            cells: cells
+                .map(|(x, y, cell_rotation)| CellSpec {
+                    x,
+                    y,
+                    rotation: (rotation + cell_rotation) % 4,
+                })
+                .collect(),
+        }
+    };
+    let outer = |nibble: usize| ShapeKind::Outer((hash[nibble] as usize % outer_len) as u8);
+
+    let mut groups = Vec::new();
+    for (start, len) in layout::rings(grid_size) {
+        // Inner rings use the next 4 nibbles after the color indices, and swap colors with the
+        // ring around them to stay distinguishable from it
+        let nibble = if start == 0 {
+            2
+        } else {
+            7 + start as usize * 4
+        };
+        let (side_role, corner_role) = if start % 2 == 0 {
+            (side_role, corner_role)
+        } else {
+            (corner_role, side_role)
+        };
+
+        let mut cells = layout::side_cells(start, len);
+        groups.push(group(
+            outer(nibble),
+            Some(nibble + 1),
+            side_role,
+            &mut cells,
+        ));
+
+        let mut cells = layout::corner_cells(start, len).into_iter();
+        groups.push(group(
+            outer(nibble + 2),
+            Some(nibble + 3),
+            corner_role,
+            &mut cells,
+        ));
+    }
+
+    let center = ShapeKind::Center((hash[1] as usize % center_len) as u8);
+    let mut cells = layout::center_cells(grid_size);
+    groups.push(group(center, None, center_role, &mut cells));
+    groups
+}
@@ -0,0 +1,211 @@
+use crate::{
+    reduce_digest, render_identicon_onto, Config, IdenticonHasher, IdenticonInput, RenderError,
+    Sha1,
+};
+use ril::prelude::*;
+
+/// Options for laying out a sprite sheet. See [`render_sprite_sheet`] for more information.
+#[derive(Clone, Debug)]
+pub struct SpriteSheetOptions {
+    /// The amount of columns in the grid. If zero, the grid is made as square as possible.
+    /// Defaults to `0`.
+    pub columns: u32,
+    /// The spacing between cells in pixels. Defaults to `0`.
+    pub spacing: u32,
+    /// The color of the sheet behind and between cells. Defaults to [`Rgba::transparent`].
+    pub background_color: Rgba,
+    /// Whether to draw the index of each cell below it. Defaults to `false`.
+    pub labels: bool,
+    /// The color of labels. Defaults to [`Rgba::black`].
+    pub label_color: Rgba,
+    /// The index of the first cell, for example to continue the numbering of a previous sheet.
+    /// Defaults to `0`.
+    pub index_offset: usize,
+}
+
+impl Default for SpriteSheetOptions {
+    fn default() -> Self {
+        Self {
+            columns: 0,
+            spacing: 0,
+            background_color: Rgba::transparent(),
+            labels: false,
+            label_color: Rgba::black(),
+            index_offset: 0,
+        }
+    }
+}
+
+/// A cell of a [`SpriteSheet`], mapping an input to the rectangle its identicon occupies.
+#[derive(Clone, Debug)]
+pub struct SpriteCell<I> {
+    /// The input the identicon was generated for.
+    pub input: I,
+    /// The index of this cell, including [`SpriteSheetOptions::index_offset`].
+    pub index: usize,
+    /// The x-coordinate of the top-left corner of the identicon in pixels.
+    pub x: u32,
+    /// The y-coordinate of the top-left corner of the identicon in pixels.
+    pub y: u32,
+    /// The width of the identicon in pixels.
+    pub width: u32,
+    /// The height of the identicon in pixels.
+    pub height: u32,
+}
+
+/// A grid of identicons rendered onto a single image.
+#[derive(Clone)]
+pub struct SpriteSheet<I> {
+    /// The rendered sheet.
+    pub image: Image<Rgba>,
+    /// The cells of the sheet, in the order of their inputs. The rectangles exclude labels, so
+    /// they can be used directly as CSS sprite offsets.
+    pub cells: Vec<SpriteCell<I>>,
+}
+
+/// 3x5 bitmaps of the digits `0` to `9`, one row per 3 bits starting from the top.
+const DIGITS: [u16; 10] = [
+    0b111_101_101_101_111,
+    0b010_110_010_010_111,
+    0b111_001_111_100_111,
+    0b111_001_111_001_111,
+    0b101_101_111_001_001,
+    0b111_100_111_001_111,
+    0b111_100_111_101_111,
+    0b111_001_001_001_001,
+    0b111_101_111_101_111,
+    0b111_101_111_001_111,
+];
+
+/// Draws `index` centered horizontally within `width` pixels from `(x, y)`, clipped to the image.
+fn draw_label(
+    image: &mut Image<Rgba>,
+    index: usize,
+    (x, y): (u32, u32),
+    width: u32,
+    scale: u32,
+    color: Rgba,
+) {
+    let text = index.to_string();
+    let text_width = (text.len() as u32 * 4 - 1) * scale;
+    let x = x + width.saturating_sub(text_width) / 2;
+    let (image_width, image_height) = (image.width(), image.height());
+
+    for (i, digit) in text.bytes().enumerate() {
+        let bitmap = DIGITS[(digit - b'0') as usize];
+        let left = x + i as u32 * 4 * scale;
+
+        for bit in 0..15 {
+            if bitmap >> (14 - bit) & 1 == 0 {
+                continue;
+            }
+
+            let (column, row) = (bit % 3, bit / 3);
+            for py in y + row * scale..y + (row + 1) * scale {
+                for px in left + column * scale..left + (column + 1) * scale {
+                    if px < image_width && py < image_height {
+                        image.set_pixel(px, py, color);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Renders identicons for many inputs onto a single grid image, which is useful to review many
+/// identicons side by side or to serve them as a CSS sprite. Inputs are hashed with SHA-1, like
+/// [`generate_identicon`](crate::generate_identicon).
+///
+/// Cells are laid out left to right, then top to bottom. Each cell is [`Config::width`] pixels
+/// wide and [`Config::height`] pixels tall, plus room for its label if
+/// [`SpriteSheetOptions::labels`] is set.
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero, or there are no
+///   inputs.
+pub fn render_sprite_sheet<I: IdenticonInput>(
+    inputs: impl IntoIterator<Item = I>,
+    config: &Config,
+    options: &SpriteSheetOptions,
+) -> Result<SpriteSheet<I>, RenderError> {
+    render_sprite_sheet_with::<Sha1, I>(inputs, config, options)
+}
+
+/// Renders identicons for many inputs onto a single grid image, hashing them with the hash
+/// algorithm `H`. See [`render_sprite_sheet`] for more information.
+///
+/// # Errors
+/// * [`RenderError::InvalidConfig`] if [`Config::validate`] fails.
+/// * [`RenderError::ZeroSize`] if [`Config::width`] or [`Config::height`] is zero, or there are no
+///   inputs.
+///
+/// # Panics
+/// * If the digest of `H` is shorter than 6 bytes. See [`reduce_digest`] for more information.
+pub fn render_sprite_sheet_with<H: IdenticonHasher, I: IdenticonInput>(
+    inputs: impl IntoIterator<Item = I>,
+    config: &Config,
+    options: &SpriteSheetOptions,
+) -> Result<SpriteSheet<I>, RenderError> {
+    config.validate()?;
+
+    let inputs = inputs.into_iter().collect::<Vec<_>>();
+    if config.width == 0 || config.height == 0 || inputs.is_empty() {
+        return Err(RenderError::ZeroSize);
+    }
+
+    let count = inputs.len() as u32;
+    let columns = match options.columns {
+        0 => (f64::from(count).sqrt().ceil() as u32).max(1),
+        columns => columns,
+    };
+    let rows = count.div_ceil(columns);
+
+    let (width, height) = (config.width, config.height);
+    let label_scale = (width.min(height) / 64).max(1);
+    let label_height = if options.labels { label_scale * 7 } else { 0 };
+    let (cell_width, cell_height) = (
+        width + options.spacing,
+        height + label_height + options.spacing,
+    );
+
+    let mut image = Image::new(
+        (columns.min(count) * cell_width).saturating_sub(options.spacing),
+        (rows * cell_height).saturating_sub(options.spacing),
+        options.background_color,
+    );
+    let mut cells = Vec::with_capacity(inputs.len());
+
+    for (i, input) in inputs.into_iter().enumerate() {
+        let (x, y) = (
+            (i as u32 % columns) * cell_width,
+            (i as u32 / columns) * cell_height,
+        );
+        let index = options.index_offset + i;
+
+        let hash = reduce_digest(H::digest(&input.identicon_bytes()).as_ref());
+        render_identicon_onto(hash, config, &mut image, (x, y))?;
+        if options.labels {
+            let position = (x, y + height + label_scale);
+            draw_label(
+                &mut image,
+                index,
+                position,
+                width,
+                label_scale,
+                options.label_color,
+            );
+        }
+
+        cells.push(SpriteCell {
+            input,
+            index,
+            x,
+            y,
+            width,
+            height,
+        });
+    }
+
+    Ok(SpriteSheet { image, cells })
+}
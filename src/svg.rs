@@ -0,0 +1,148 @@
+use crate::renderer::Renderer;
+use ril::Rgba;
+use std::fmt::Write;
+
+/// Rounds a coordinate to one decimal place for use in SVG path data, the same way jdenticon
+/// does.
+fn svg_value(value: f64) -> f64 {
+    (value * 10.0 + 0.5).trunc() / 10.0
+}
+
+/// Formats a color as a `#rrggbb` hex string, ignoring its alpha channel.
+fn svg_color(color: Rgba) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+/// Writes the `fill` (and if necessary, `fill-opacity`) attributes for the given color.
+fn write_fill(out: &mut String, color: Rgba) {
+    let _ = write!(out, r#"fill="{}""#, svg_color(color));
+    if color.a < 255 {
+        let _ = write!(out, r#" fill-opacity="{:.2}""#, color.a as f64 / 255.0);
+    }
+}
+
+/// A [`Renderer`] that builds an SVG document.
+///
+/// Shapes are collected as path data grouped by color, and emitted as one `<path>` element per
+/// color. Inverted shapes are drawn with a reversed winding, which cuts holes into the shapes
+/// they are drawn over.
+pub struct SvgRenderer {
+    width: u32,
+    height: u32,
+    background_color: Rgba,
+    /// Path data for each color, in the order each color was first used.
+    paths: Vec<(Rgba, String)>,
+    current: usize,
+}
+
+impl SvgRenderer {
+    /// Creates a new SVG renderer for a square icon of the given size in pixels.
+    #[must_use]
+    pub const fn new(size: u32) -> Self {
+        Self::with_dimensions(size, size)
+    }
+
+    /// Creates a new SVG renderer for an icon of the given width and height in pixels.
+    #[must_use]
+    pub const fn with_dimensions(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            background_color: Rgba::transparent(),
+            paths: Vec::new(),
+            current: 0,
+        }
+    }
+
+    fn path(&mut self) -> &mut String {
+        debug_assert!(
+            self.current < self.paths.len(),
+            "Renderer::begin_shape must be called before drawing shapes",
+        );
+        &mut self.paths[self.current].1
+    }
+
+    /// Returns the opening `<svg>` tag of a document of the given width and height.
+    pub(crate) fn open_tag(width: u32, height: u32) -> String {
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+            width, height,
+        )
+    }
+
+    /// Consumes the renderer and writes the background and paths of the document, without the
+    /// enclosing `<svg>` element.
+    pub(crate) fn write_contents(self, out: &mut String) {
+        if self.background_color.a > 0 {
+            let _ = write!(
+                out,
+                r#"<rect width="100%" height="100%" fill="{}" opacity="{:.2}"/>"#,
+                svg_color(self.background_color),
+                self.background_color.a as f64 / 255.0,
+            );
+        }
+
+        for (color, data) in self.paths {
+            if color.a == 0 || data.is_empty() {
+                continue;
+            }
+            out.push_str("<path ");
+            write_fill(out, color);
+            let _ = write!(out, r#" d="{data}"/>"#);
+        }
+    }
+
+    /// Consumes the renderer and returns the SVG document.
+    #[must_use]
+    pub fn finish(self) -> String {
+        let mut out = Self::open_tag(self.width, self.height);
+        self.write_contents(&mut out);
+        out.push_str("</svg>");
+        out
+    }
+}
+
+impl Renderer for SvgRenderer {
+    fn background(&mut self, color: Rgba) {
+        self.background_color = color;
+    }
+
+    fn begin_shape(&mut self, color: Rgba) {
+        self.current = match self.paths.iter().position(|(c, _)| *c == color) {
+            Some(index) => index,
+            None => {
+                self.paths.push((color, String::new()));
+                self.paths.len() - 1
+            }
+        };
+    }
+
+    fn polygon(&mut self, points: &[(f64, f64)], _invert: bool) {
+        let path = self.path();
+        for (i, &(x, y)) in points.iter().enumerate() {
+            let command = if i == 0 { 'M' } else { 'L' };
+            let _ = write!(path, "{command}{} {}", svg_value(x), svg_value(y));
+        }
+        path.push('Z');
+    }
+
+    fn circle(&mut self, top_left: (f64, f64), diameter: f64, invert: bool) {
+        self.ellipse(top_left, (diameter, diameter), invert);
+    }
+
+    fn ellipse(&mut self, (x, y): (f64, f64), (w, h): (f64, f64), invert: bool) {
+        let sweep = u8::from(!invert);
+        let (rx, ry) = (svg_value(w / 2.0), svg_value(h / 2.0));
+        let arc = format!("a{rx},{ry} 0 1,{sweep} ");
+
+        let _ = write!(
+            self.path(),
+            "M{} {}{arc}{},0{arc}{},0",
+            svg_value(x),
+            svg_value(y + h / 2.0),
+            svg_value(w),
+            // Avoid formatting `-0`
+            0.0 - svg_value(w),
+        );
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{hsl::mirror_hsl_lightness, Config, ConfigBuilderError};
+
+/// A color scheme that a [`ThemedConfig`] has a variant for.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum Theme {
+    /// The light variant, for light backgrounds.
+    #[default]
+    Light,
+    /// The dark variant, for dark backgrounds.
+    Dark,
+}
+
+/// A pair of configurations for light and dark color schemes.
+///
+/// Render either variant by passing [`ThemedConfig::get`] to any of the render functions, or both
+/// at once with [`render_identicon_themed_svg`](crate::render_identicon_themed_svg), which emits a
+/// single SVG document that switches between them with the `prefers-color-scheme` media query.
+///
+/// Both variants must have the same width and height. Anything the hash decides, such as the
+/// shapes and color roles, is the same for both variants as long as they share their other
+/// options.
+#[derive(Clone, Debug)]
+pub struct ThemedConfig {
+    /// The configuration of the light variant.
+    pub light: Config,
+    /// The configuration of the dark variant.
+    pub dark: Config,
+}
+
+impl ThemedConfig {
+    /// Creates a new themed configuration from both of its variants.
+    #[must_use]
+    pub const fn new(light: Config, dark: Config) -> Self {
+        Self { light, dark }
+    }
+
+    /// Creates a new themed configuration from its light variant, deriving the dark variant from
+    /// it.
+    ///
+    /// The dark variant flips [`Config::mirror_lightness`], so that every color role takes
+    /// `1 - l` of the lightness `l` it has in the light variant, and the background color has its
+    /// HSL lightness mirrored while keeping its hue. For example, the dark gray role becomes light
+    /// and a white background becomes black, so roles that stand out against the light background
+    /// also stand out against the dark one. All other options are kept as they are.
+    #[must_use]
+    pub fn from_light(light: Config) -> Self {
+        let dark = Config {
+            mirror_lightness: !light.mirror_lightness,
+            background_color: mirror_hsl_lightness(light.background_color),
+            ..light.clone()
+        };
+
+        Self { light, dark }
+    }
+
+    /// Returns the configuration of the given variant.
+    #[must_use]
+    pub const fn get(&self, theme: Theme) -> &Config {
+        match theme {
+            Theme::Light => &self.light,
+            Theme::Dark => &self.dark,
+        }
+    }
+
+    /// Validates both variants. See [`Config::validate`] for more information.
+    ///
+    /// # Errors
+    /// * If either variant is invalid.
+    /// * [`ConfigBuilderError::MismatchedThemeSize`] if the variants do not have the same width
+    ///   and height.
+    pub fn validate(&self) -> Result<(), ConfigBuilderError> {
+        self.light.validate()?;
+        self.dark.validate()?;
+
+        if (self.light.width, self.light.height) != (self.dark.width, self.dark.height) {
+            return Err(ConfigBuilderError::MismatchedThemeSize);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! WebAssembly bindings, enabled by the `wasm` feature.
+//!
+//! Configurations are plain JS objects whose keys match the [`Config`](crate::Config) fields, for
+//! example `{ width: 128, height: 64, alignment: "tile", hues: [207], color_lightness: [0.4, 0.8],
+//! background_color: "#ffffffff" }`. Missing keys take their default values, `size` sets both
+//! `width` and `height`, and `undefined` or `null` can be passed for the default configuration.
+//!
+//! Raster output is returned as RGBA pixels in a `Uint8ClampedArray`, ready to be wrapped in an
+//! `ImageData`. The pixels are the same as those of [`render_identicon`](crate::render_identicon)
+//! on a server for the same hash and configuration. No image encoder is needed, so build with `default-features = false` to leave out
+//! ril's PNG encoder. The library is only built as an `rlib` by default, so request a `cdylib`
+//! explicitly and generate the JS glue with `wasm-bindgen`:
+//!
+//! ```sh
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --no-default-features \
+//!     --features wasm --crate-type cdylib
+//! wasm-bindgen --target web --out-dir pkg target/wasm32-unknown-unknown/release/rdenticon.wasm
+//! ```
+
+use crate::{reduce_digest, render_identicon_into, Config, IdenticonHasher, Sha1};
+use wasm_bindgen::{prelude::*, Clamped};
+
+fn config_from_js(config: JsValue) -> Result<Config, JsError> {
+    if config.is_undefined() || config.is_null() {
+        return Ok(Config::default());
+    }
+
+    serde_wasm_bindgen::from_value(config).map_err(|err| JsError::new(&err.to_string()))
+}
+
+fn hash_from_js(hash: &[u8]) -> Result<[u8; 20], JsError> {
+    match hash.len() {
+        20 => Ok(hash.try_into().expect("hash is 20 bytes long")),
+        6.. => Ok(reduce_digest(hash)),
+        _ => Err(JsError::new("hash must be at least 6 bytes long")),
+    }
+}
+
+fn hash_message(message: &str) -> [u8; 20] {
+    reduce_digest(Sha1::digest(message.as_bytes()).as_ref())
+}
+
+/// Renders RGBA pixels the same way as [`render_identicon`](crate::render_identicon), so that
+/// icons rendered on the web match those rendered on a server.
+fn render_rgba(hash: [u8; 20], config: &Config) -> Result<Clamped<Vec<u8>>, JsError> {
+    config.validate()?;
+
+    // `usize` is 32 bits on wasm32, so large icons may not be addressable at all
+    let (width, height) = (config.width as usize, config.height as usize);
+    let len = width
+        .checked_mul(height)
+        .and_then(|len| len.checked_mul(4))
+        .ok_or_else(|| JsError::new("icon is too large"))?;
+    let mut buffer = Vec::new();
+    buffer
+        .try_reserve_exact(len)
+        .map_err(|_| JsError::new("icon is too large"))?;
+    buffer.resize(len, 0);
+    render_identicon_into(hash, config, &mut buffer, width * 4)?;
+
+    Ok(Clamped(buffer))
+}
+
+/// Generates an identicon for the given message, hashed with SHA-1, as RGBA pixels.
+///
+/// # Errors
+/// * If the configuration is invalid.
+/// * If the icon is too large to be held in memory.
+#[wasm_bindgen(js_name = generateIdenticon)]
+pub fn generate_identicon(message: &str, config: JsValue) -> Result<Clamped<Vec<u8>>, JsError> {
+    render_rgba(hash_message(message), &config_from_js(config)?)
+}
+
+/// Generates an identicon for the given message, hashed with SHA-1, as an SVG document.
+///
+/// # Errors
+/// * If the configuration is invalid.
+#[wasm_bindgen(js_name = generateIdenticonSvg)]
+pub fn generate_identicon_svg(message: &str, config: JsValue) -> Result<String, JsError> {
+    let config = config_from_js(config)?;
+    config.validate()?;
+
+    Ok(crate::render_identicon_svg(hash_message(message), &config))
+}
+
+/// Renders an identicon for the given hash as RGBA pixels. Hashes that are not 20 bytes long are
+/// reduced with [`reduce_digest`].
+///
+/// # Errors
+/// * If the configuration is invalid.
+/// * If the icon is too large to be held in memory.
+/// * If the hash is shorter than 6 bytes.
+#[wasm_bindgen(js_name = renderIdenticon)]
+pub fn render_identicon(hash: &[u8], config: JsValue) -> Result<Clamped<Vec<u8>>, JsError> {
+    render_rgba(hash_from_js(hash)?, &config_from_js(config)?)
+}
+
+/// Renders an identicon for the given hash as an SVG document. Hashes that are not 20 bytes long
+/// are reduced with [`reduce_digest`].
+///
+/// # Errors
+/// * If the configuration is invalid.
+/// * If the hash is shorter than 6 bytes.
+#[wasm_bindgen(js_name = renderIdenticonSvg)]
+pub fn render_identicon_svg(hash: &[u8], config: JsValue) -> Result<String, JsError> {
+    let config = config_from_js(config)?;
+    config.validate()?;
+
+    Ok(crate::render_identicon_svg(hash_from_js(hash)?, &config))
+}
+
+/// Describes the identicon for the given message, hashed with SHA-1, in English. This is useful
+/// as alt text for screen readers. See [`describe`](crate::describe) for more information.
+///
+/// # Errors
+/// * If the configuration is invalid.
+#[wasm_bindgen(js_name = describeIdenticon)]
+pub fn describe_identicon(message: &str, config: JsValue) -> Result<String, JsError> {
+    let config = config_from_js(config)?;
+    config.validate()?;
+
+    Ok(crate::describe(hash_message(message), &config))
+}
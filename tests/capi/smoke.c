@@ -0,0 +1,64 @@
+/* Smoke test for the C bindings. Build and run it with `make -C tests/capi`. */
+
+#include <assert.h>
+#include <stdio.h>
+#include <string.h>
+
+#include "rdenticon.h"
+
+int main(void) {
+  RdenticonConfig *config = rdenticon_config_new();
+  assert(config != NULL);
+
+  const double hues[] = {120.0, 240.0};
+  assert(rdenticon_config_set_hues(config, hues, 2) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_size(config, 64) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_background_color(config, 255, 255, 255, 255) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_build(config) == RDENTICON_ERROR_OK);
+
+  uint8_t *pixels = NULL;
+  size_t len = 0;
+  assert(rdenticon_generate_rgba("rdenticon", config, &pixels, &len) == RDENTICON_ERROR_OK);
+  assert(len == 64 * 64 * 4);
+  /* The corner is padding, so it has the background color */
+  assert(pixels[0] == 255 && pixels[1] == 255 && pixels[2] == 255 && pixels[3] == 255);
+  rdenticon_rgba_free(pixels, len);
+
+  const uint8_t hash[20] = {0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23,
+                            0x45, 0x67, 0x89, 0xab, 0xcd, 0xef, 0x01, 0x23, 0x45, 0x67};
+  char *svg = NULL;
+  assert(rdenticon_render_svg(hash, config, &svg) == RDENTICON_ERROR_OK);
+  assert(strncmp(svg, "<svg", 4) == 0);
+  rdenticon_string_free(svg);
+
+  assert(rdenticon_config_set_padding(config, 0.75) == RDENTICON_ERROR_OK);
+  RdenticonError error = rdenticon_config_build(config);
+  assert(error == RDENTICON_ERROR_INVALID_PADDING);
+  assert(rdenticon_render_svg(hash, config, &svg) == RDENTICON_ERROR_INVALID_PADDING);
+  printf("expected error: %s\n", rdenticon_error_message(error));
+
+  assert(rdenticon_config_set_size(NULL, 64) == RDENTICON_ERROR_NULL_POINTER);
+
+  /* Enum arguments outside of their enum are rejected */
+  assert(rdenticon_config_set_alignment(config, RDENTICON_ALIGNMENT_TILE) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_alignment(config, 42) == RDENTICON_ERROR_INVALID_ARGUMENT);
+  assert(rdenticon_config_set_color_space(config, 42) == RDENTICON_ERROR_INVALID_ARGUMENT);
+  assert(rdenticon_config_set_antialiasing(config, 42, 4) == RDENTICON_ERROR_INVALID_ARGUMENT);
+  assert(strcmp(rdenticon_error_message(42), "unknown error code") == 0);
+
+  /* Boolean arguments other than 0 and 1 are rejected */
+  assert(rdenticon_config_set_jdenticon_compat(config, 1) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_jdenticon_compat(config, 0) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_jdenticon_compat(config, 2) == RDENTICON_ERROR_INVALID_ARGUMENT);
+  assert(rdenticon_config_set_mirror_lightness(config, 2) == RDENTICON_ERROR_INVALID_ARGUMENT);
+
+  /* Icons too large to allocate are rejected instead of aborting */
+  assert(rdenticon_config_set_jdenticon_compat(config, 0) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_padding(config, 0.1) == RDENTICON_ERROR_OK);
+  assert(rdenticon_config_set_size(config, UINT32_MAX) == RDENTICON_ERROR_OK);
+  assert(rdenticon_generate_rgba("rdenticon", config, &pixels, &len) == RDENTICON_ERROR_TOO_LARGE);
+  rdenticon_config_free(config);
+
+  puts("ok");
+  return 0;
+}
@@ -0,0 +1,69 @@
+//! Conformance tests comparing `jdenticon_compat` SVG output against jdenticon's `toSvg`.
+//!
+//! The reference table is generated by `tests/jdenticon/generate.js`.
+
+use rdenticon::{render_identicon_svg, Config, Rgba};
+
+const REFERENCE: &str = include_str!("jdenticon/reference.tsv");
+
+fn parse_range(s: &str) -> std::ops::RangeInclusive<f64> {
+    let (start, end) = s.split_once(',').expect("invalid range");
+    start.parse().unwrap()..=end.parse().unwrap()
+}
+
+#[test]
+fn test_jdenticon_conformance() {
+    let mut failures = Vec::new();
+    let mut lines = REFERENCE.lines();
+    lines.next().expect("missing header");
+
+    for line in lines {
+        let fields = line.split('\t').collect::<Vec<_>>();
+        let [value, size, padding, hues, color_lightness, grayscale_lightness, color_saturation, grayscale_saturation, back_color, expected] =
+            fields[..]
+        else {
+            panic!("invalid reference line: {line}");
+        };
+
+        let hues = hues
+            .split(',')
+            .filter(|hue| !hue.is_empty())
+            .map(|hue| hue.parse().unwrap())
+            .collect::<Vec<f64>>();
+
+        // jdenticon's default background is transparent
+        let background_color = if back_color.is_empty() {
+            Rgba::transparent()
+        } else {
+            rdenticon::parse_hex_color(back_color).unwrap()
+        };
+
+        let config = Config::builder()
+            .size(size.parse().unwrap())
+            .padding(padding.parse().unwrap())
+            .hues(hues)
+            .color_lightness(parse_range(color_lightness))
+            .grayscale_lightness(parse_range(grayscale_lightness))
+            .color_saturation(color_saturation.parse().unwrap())
+            .grayscale_saturation(grayscale_saturation.parse().unwrap())
+            .background_color(background_color)
+            .jdenticon_compat(true)
+            .build()
+            .expect("invalid config");
+
+        let hash = sha1_smol::Sha1::from(value).digest().bytes();
+        let actual = render_identicon_svg(hash, &config);
+        if actual != expected {
+            failures.push(format!(
+                "value {value:?}, size {size}\n  expected: {expected}\n  actual:   {actual}"
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} mismatches:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
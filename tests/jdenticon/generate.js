@@ -0,0 +1,70 @@
+// Regenerates `reference.tsv`, the table of jdenticon outputs that `tests/jdenticon.rs` compares
+// rdenticon's `jdenticon_compat` mode against.
+//
+// Usage:
+//     npm install jdenticon@3
+//     node tests/jdenticon/generate.js > tests/jdenticon/reference.tsv
+
+const jdenticon = require("jdenticon");
+
+const VALUES = [
+    "", "a", "sample", "hello", "jdenticon", "rdenticon", "Icon", "icon", "user@example.com",
+    "Alice", "Bob", "Charlie", "super-cool-username", "Hello, world!", "The quick brown fox",
+    "0", "42", "123456789", "admin", "root", "guest", "zebra", "yellow submarine", "🦀",
+];
+
+const SIZES = [16, 24, 32, 37, 48, 64, 80, 100, 128, 256];
+
+const CONFIGS = [
+    {},
+    { backColor: "#ffffffff" },
+    { backColor: "#2228", padding: 0 },
+    { padding: 0.2 },
+    { hues: [134] },
+    { hues: [0, 120, 240], backColor: "#000000ff" },
+    {
+        lightness: { color: [0.2, 0.6], grayscale: [0.1, 0.5] },
+        saturation: { color: 1, grayscale: 0.3 },
+    },
+    {
+        hues: [45, 300],
+        lightness: { color: [0.55, 0.95], grayscale: [0.4, 1] },
+        saturation: { color: 0.25, grayscale: 0 },
+        padding: 0.35,
+    },
+];
+
+const range = (value, fallback) => (value || fallback).join(",");
+
+console.log(
+    [
+        "value", "size", "padding", "hues", "color_lightness", "grayscale_lightness",
+        "color_saturation", "grayscale_saturation", "back_color", "svg",
+    ].join("\t"),
+);
+
+VALUES.forEach((value, i) => {
+    // Cycle through sizes and configurations so every combination is covered a few times
+    // without making the table unreasonably large.
+    for (let j = 0; j < 4; j++) {
+        const size = SIZES[(i + j * 3) % SIZES.length];
+        const config = CONFIGS[(i + j) % CONFIGS.length];
+        const lightness = config.lightness || {};
+        const saturation = config.saturation || {};
+
+        console.log(
+            [
+                value,
+                size,
+                config.padding ?? 0.08,
+                (config.hues || []).join(","),
+                range(lightness.color, [0.4, 0.8]),
+                range(lightness.grayscale, [0.3, 0.9]),
+                saturation.color ?? 0.5,
+                saturation.grayscale ?? 0,
+                config.backColor || "",
+                jdenticon.toSvg(value, size, config),
+            ].join("\t"),
+        );
+    }
+});